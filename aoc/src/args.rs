@@ -0,0 +1,107 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::days::{self, Day};
+
+#[derive(Debug, Default)]
+pub struct Args {
+    pub command: Option<String>,
+    pub positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut result = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("missing value for --{name}"))?;
+                result.options.insert(name.to_owned(), value);
+            } else if result.command.is_none() {
+                result.command = Some(arg);
+            } else {
+                result.positional.push(arg);
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn parsed_option<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.option(name)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| format!("invalid value '{v}' for --{name}"))
+            })
+            .transpose()
+    }
+
+    /// The single day named by the positional arguments.
+    pub fn day(&self) -> Result<Day, String> {
+        match &self.positional[..] {
+            [day] => parse_day(day),
+            [] => Err("no day given".to_owned()),
+            _ => Err("expected a single day".to_owned()),
+        }
+    }
+
+    /// The day and then the expression given to `query`.
+    pub fn day_and_query(&self) -> Result<(Day, &str), String> {
+        match &self.positional[..] {
+            [day, query] => Ok((parse_day(day)?, query)),
+            _ => Err("expected a day and a query".to_owned()),
+        }
+    }
+
+    /// Every day named by the positional arguments.
+    pub fn days(&self) -> Result<Vec<Day>, String> {
+        self.positional.iter().map(|d| parse_day(d)).collect()
+    }
+}
+
+fn parse_day(day: &str) -> Result<Day, String> {
+    day.trim_start_matches("day")
+        .parse()
+        .ok()
+        .and_then(days::find)
+        .ok_or_else(|| format!("no solution for day '{day}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Args {
+        Args::parse(args.iter().map(ToString::to_string)).unwrap()
+    }
+
+    #[test]
+    fn positional_and_options() {
+        let args = parse(&["check", "8", "--input", "other.txt"]);
+        assert_eq!(args.command.as_deref(), Some("check"));
+        assert_eq!(args.positional, vec!["8"]);
+        assert_eq!(args.option("input"), Some("other.txt"));
+    }
+
+    #[test]
+    fn parsed_options() {
+        let args = parse(&["gen", "8", "--seed", "3", "--size", "x"]);
+        assert_eq!(args.parsed_option::<u64>("seed"), Ok(Some(3)));
+        assert!(args.parsed_option::<usize>("size").is_err());
+        assert_eq!(args.parsed_option::<u64>("missing"), Ok(None));
+    }
+
+    #[test]
+    fn day_names() {
+        assert_eq!(parse(&["run", "day08"]).day().unwrap().number, 8);
+        assert_eq!(parse(&["run", "08"]).day().unwrap().number, 8);
+        assert!(parse(&["run", "99"]).day().is_err());
+    }
+}
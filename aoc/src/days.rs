@@ -0,0 +1,273 @@
+use std::{fmt::Display, ops::ControlFlow, path::PathBuf};
+
+pub type Solver = fn(&str) -> String;
+/// Called by long-running solves with the number of steps taken so far;
+/// breaking cancels the solve.
+pub type Progress<'a> = dyn FnMut(usize) -> ControlFlow<()> + 'a;
+/// A solve that reports progress and returns `None` once cancelled.
+pub type ProgressSolver = fn(&str, &mut Progress) -> Option<String>;
+pub type Checker = fn(&str) -> Vec<String>;
+/// Finds the parts of an input matching a query, or explains why the query is
+/// invalid.
+pub type Querier = fn(&str, &str) -> Result<Vec<String>, String>;
+/// Solves both parts of a day whose parts share work on the input, doing that
+/// work once. Gives part a's answer, scored by the rule of the given name if
+/// there is one, and a solver for part b that reuses the work.
+pub type SharedSolver = fn(&str, Option<&str>) -> Result<(String, PartB), String>;
+/// Solves part b from the work it shares with part a.
+pub type PartB = Box<dyn FnOnce() -> String>;
+/// Summarises an input, or explains why it can't be read.
+pub type Reporter = fn(&str, ReportFormat) -> Result<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    Csv,
+}
+/// Builds an input from a seed and size, with the answers it was built to have.
+pub type Generator = fn(u64, usize) -> Generated;
+
+pub struct Generated {
+    pub input: String,
+    pub answer_a: Option<String>,
+    pub answer_b: Option<String>,
+}
+
+impl<A: Display, B: Display> From<(String, Option<A>, Option<B>)> for Generated {
+    fn from((input, a, b): (String, Option<A>, Option<B>)) -> Self {
+        Self {
+            input,
+            answer_a: a.map(|a| a.to_string()),
+            answer_b: b.map(|b| b.to_string()),
+        }
+    }
+}
+
+pub struct Day {
+    pub number: u8,
+    pub solve_a: Solver,
+    pub solve_b: Solver,
+    pub generate: Generator,
+    pub check: Option<Checker>,
+    pub progress: Option<[ProgressSolver; 2]>,
+    pub query: Option<Querier>,
+    pub stats: Option<Reporter>,
+    pub shared: Option<SharedSolver>,
+    /// Whether part a can be scored by a named rule, given to `shared`.
+    pub scoring: bool,
+}
+
+impl Day {
+    fn new(number: u8, solve_a: Solver, solve_b: Solver, generate: Generator) -> Self {
+        Self {
+            number,
+            solve_a,
+            solve_b,
+            generate,
+            check: None,
+            progress: None,
+            query: None,
+            stats: None,
+            shared: None,
+            scoring: false,
+        }
+    }
+
+    fn with_check(mut self, check: Checker) -> Self {
+        self.check = Some(check);
+        self
+    }
+
+    fn with_progress(mut self, solve_a: ProgressSolver, solve_b: ProgressSolver) -> Self {
+        self.progress = Some([solve_a, solve_b]);
+        self
+    }
+
+    fn with_query(mut self, query: Querier) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    fn with_stats(mut self, stats: Reporter) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    fn with_shared(mut self, shared: SharedSolver) -> Self {
+        self.shared = Some(shared);
+        self
+    }
+
+    fn with_scoring(mut self) -> Self {
+        self.scoring = true;
+        self
+    }
+
+    /// The crate holding the day's solution.
+    pub fn crate_dir(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(format!("day{:02}", self.number))
+    }
+
+    /// The puzzle input checked in alongside the day's solution.
+    pub fn input_path(&self) -> PathBuf {
+        self.crate_dir().join("src").join("input.txt")
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::new(
+            1,
+            |i| day01::solve_a(i).to_string(),
+            |i| day01::solve_b(i).to_string(),
+            |seed, size| day01::generate(seed, size).into(),
+        ),
+        Day::new(
+            2,
+            |i| day02::solve_a(i).to_string(),
+            |i| day02::solve_b(i).to_string(),
+            |seed, size| day02::generate(seed, size).into(),
+        )
+        .with_query(|i, q| {
+            day02::query(i, q)
+                .map(|games| games.iter().map(ToString::to_string).collect())
+                .map_err(|e| e.to_string())
+        })
+        .with_stats(|i, format| {
+            let games = i
+                .parse()
+                .map_err(|e: day02::ParseGameError| e.to_string())?;
+            let stats = day02::Stats::new(&games, &day02::standard_bag(), 5);
+            Ok(match format {
+                ReportFormat::Table => stats.table(),
+                ReportFormat::Csv => stats.csv(),
+            })
+        }),
+        Day::new(
+            3,
+            |i| day03::solve_a(i).to_string(),
+            |i| day03::solve_b(i).to_string(),
+            |seed, size| day03::generate(seed, size).into(),
+        ),
+        Day::new(
+            4,
+            |i| day04::solve_a(i).to_string(),
+            |i| day04::solve_b(i).to_string(),
+            |seed, size| day04::generate(seed, size).into(),
+        )
+        .with_check(day04::check)
+        .with_shared(|i, name| {
+            let scoring = match name {
+                Some(name) => day04::scoring(name).map_err(|e| e.to_string())?,
+                None => Box::new(day04::Doubling),
+            };
+            let matches = day04::matches(i);
+            let points = day04::points(&matches, &*scoring);
+            Ok((
+                points.to_string(),
+                Box::new(move || {
+                    day04::Cascade::default()
+                        .total::<u64>(&matches)
+                        .unwrap_or_else(|e| panic!("{e}"))
+                        .to_string()
+                }),
+            ))
+        })
+        .with_scoring(),
+        Day::new(
+            5,
+            |i| day05::solve_a(i).to_string(),
+            |i| day05::solve_b(i).to_string(),
+            |seed, size| day05::generate(seed, size).into(),
+        ),
+        Day::new(
+            6,
+            |i| day06::solve_a(i).to_string(),
+            |i| day06::solve_b(i).to_string(),
+            |seed, size| day06::generate(seed, size).into(),
+        ),
+        Day::new(
+            7,
+            |i| day07::solve_a(i).to_string(),
+            |i| day07::solve_b(i).to_string(),
+            |seed, size| day07::generate(seed, size).into(),
+        ),
+        Day::new(
+            8,
+            |i| day08::solve_a(i).to_string(),
+            |i| day08::solve_b(i).to_string(),
+            |seed, size| day08::generate(seed, size).into(),
+        )
+        .with_check(day08::check)
+        .with_progress(
+            |i, p| day08::solve_a_with(i, p).map(|a| a.to_string()),
+            |i, p| day08::solve_b_with(i, p).map(|b| b.to_string()),
+        ),
+        Day::new(
+            9,
+            |i| day09::solve_a(i).to_string(),
+            |i| day09::solve_b(i).to_string(),
+            |seed, size| day09::generate(seed, size).into(),
+        ),
+        Day::new(
+            10,
+            |i| day10::solve_a(i).to_string(),
+            |i| day10::solve_b(i).to_string(),
+            |seed, size| day10::generate(seed, size).into(),
+        )
+        .with_check(day10::check),
+        Day::new(
+            11,
+            |i| day11::solve_a(i).to_string(),
+            |i| day11::solve_b(i).to_string(),
+            |seed, size| day11::generate(seed, size).into(),
+        ),
+        Day::new(
+            12,
+            |i| day12::solve_a(i).to_string(),
+            |i| day12::solve_b(i).to_string(),
+            |seed, size| day12::generate(seed, size).into(),
+        )
+        .with_progress(
+            |i, p| day12::solve_a_with(i, p).map(|a| a.to_string()),
+            |i, p| day12::solve_b_with(i, p).map(|b| b.to_string()),
+        ),
+        Day::new(
+            13,
+            |i| day13::solve_a(i).to_string(),
+            |i| day13::solve_b(i).to_string(),
+            |seed, size| day13::generate(seed, size).into(),
+        )
+        .with_check(day13::check),
+        Day::new(
+            14,
+            |i| day14::solve_a(i).to_string(),
+            |i| day14::solve_b(i).to_string(),
+            |seed, size| day14::generate(seed, size).into(),
+        )
+        .with_check(day14::check)
+        .with_progress(
+            |i, _| Some(day14::solve_a(i).to_string()),
+            |i, p| day14::solve_b_with(i, p).map(|b| b.to_string()),
+        ),
+        Day::new(
+            15,
+            |i| day15::solve_a(i).to_string(),
+            |i| day15::solve_b(i).to_string(),
+            |seed, size| day15::generate(seed, size).into(),
+        ),
+        Day::new(
+            16,
+            |i| day16::solve_a(i).to_string(),
+            |i| day16::solve_b(i).to_string(),
+            |seed, size| day16::generate(seed, size).into(),
+        ),
+    ]
+}
+
+pub fn find(number: u8) -> Option<Day> {
+    all().into_iter().find(|d| d.number == number)
+}
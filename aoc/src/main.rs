@@ -0,0 +1,269 @@
+#![warn(clippy::pedantic)]
+#![warn(clippy::style)]
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::missing_panics_doc)]
+#![warn(clippy::use_self)]
+
+mod args;
+mod days;
+mod profile;
+mod watch;
+
+use std::{
+    fs,
+    io::{self, Write},
+    ops::ControlFlow,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use args::Args;
+use days::{Day, ProgressSolver, ReportFormat, Solver};
+
+#[cfg(feature = "alloc-profile")]
+#[global_allocator]
+static GLOBAL: profile::Counting = profile::Counting;
+
+const USAGE: &str = "usage: aoc <command> [args]
+
+commands:
+  run [DAY...]    solve both parts on the real input and time them
+  check DAY       check the input against the assumptions the solution makes
+  gen DAY         generate an input, with the answers it was built to have
+  watch DAY       rebuild, test and time the day whenever its files change
+  query DAY EXPR  list the parts of the input matching EXPR (day 2 only)
+  stats DAY       summarise the input (day 2 only)
+
+options:
+  --input PATH    read the input from PATH instead of the day's input.txt
+  --seed N        seed for gen (default 0)
+  --size S        size of the input gen builds, in the day's own units
+  --output PATH   write the generated input to PATH instead of stdout
+  --format tsv    print run results as day, part, answer and nanoseconds
+  --format csv    print stats as CSV rather than tables
+  --timeout SECS  give up on a part after SECS seconds, where the day's
+                  solution reports its progress
+  --scoring NAME  score part a with doubling, linear, fibonacci or
+                  table:P0,P1,... (day 4 only)
+
+build with `--features alloc-profile` to also report allocations, bytes
+allocated, peak live heap bytes and, on Linux, peak resident bytes for each
+part";
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => return usage_error(&e),
+    };
+
+    let result = match args.command.as_deref() {
+        Some("run") => run(&args),
+        Some("check") => check(&args),
+        Some("gen") => generate(&args),
+        Some("watch") => watch::watch(&args),
+        Some("query") => query(&args),
+        Some("stats") => stats(&args),
+        Some(c) => Err(format!("unknown command '{c}'")),
+        None => Err("no command given".to_owned()),
+    };
+
+    result.unwrap_or_else(|e| usage_error(&e))
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {message}\n\n{USAGE}");
+    ExitCode::from(2)
+}
+
+fn read_input(day: &Day, args: &Args) -> Result<String, String> {
+    let path = args
+        .option("input")
+        .map_or_else(|| day.input_path(), Into::into);
+    fs::read_to_string(&path).map_err(|e| format!("reading {}: {e}", path.display()))
+}
+
+fn run(args: &Args) -> Result<ExitCode, String> {
+    let days = if args.positional.is_empty() {
+        days::all()
+    } else {
+        args.days()?
+    };
+
+    let tsv = match args.option("format") {
+        None | Some("plain") => false,
+        Some("tsv") => true,
+        Some(f) => return Err(format!("unknown format '{f}'")),
+    };
+
+    let budget = args
+        .parsed_option("timeout")?
+        .map(|secs| {
+            Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid timeout {secs}"))
+        })
+        .transpose()?;
+
+    let scoring = args.option("scoring");
+    if let Some(day) = days.iter().find(|d| scoring.is_some() && !d.scoring) {
+        return Err(format!("day {:02} has no scoring rules", day.number));
+    }
+
+    let mut timed_out = false;
+    for day in days {
+        let input = read_input(&day, args)?;
+        // part a of a day with shared work leaves part b's solver here
+        let mut part_b = None;
+        for (i, (part, solve)) in [("a", day.solve_a), ("b", day.solve_b)]
+            .into_iter()
+            .enumerate()
+        {
+            // shared solvers don't report their progress, so can't be stopped
+            let progress = day.progress.map(|p| p[i]).filter(|_| day.shared.is_none());
+            if budget.is_some() && progress.is_none() {
+                eprintln!(
+                    "warning: day {:02} {part} can't be stopped early, so runs without the timeout",
+                    day.number
+                );
+            }
+            let m = profile::measure(|| match (day.shared, part_b.take()) {
+                (Some(shared), None) => shared(&input, scoring).map(|(a, b)| {
+                    part_b = Some(b);
+                    Ok(a)
+                }),
+                (_, Some(solve_b)) => Ok(Ok(solve_b())),
+                (None, None) => Ok(solve_within(solve, progress, &input, budget)),
+            });
+            let value = match m.value {
+                Ok(value) => value.unwrap_or_else(|step| {
+                    timed_out = true;
+                    format!("timed out at step {step}")
+                }),
+                Err(e) => {
+                    eprintln!("error: day {:02} {part}: {e}", day.number);
+                    return Ok(ExitCode::FAILURE);
+                }
+            };
+
+            if tsv {
+                println!("{}\t{part}\t{value}\t{}", day.number, m.elapsed.as_nanos());
+                continue;
+            }
+            print!(
+                "day {:02} {part}: {value:>20} {:>12.2?}",
+                day.number, m.elapsed
+            );
+            match m.memory {
+                Some(memory) => println!(" {memory}"),
+                None => println!(),
+            }
+        }
+    }
+
+    Ok(if timed_out {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Solves one part, giving up with the step reached once `budget` has passed.
+///
+/// Days that don't report progress always run to the end.
+fn solve_within(
+    solve: Solver,
+    progress: Option<ProgressSolver>,
+    input: &str,
+    budget: Option<Duration>,
+) -> Result<String, usize> {
+    let (Some(budget), Some(solve)) = (budget, progress) else {
+        return Ok(solve(input));
+    };
+
+    let start = Instant::now();
+    let mut reached = 0;
+    solve(input, &mut |step| {
+        reached = step;
+        if start.elapsed() < budget {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    })
+    .ok_or(reached)
+}
+
+fn check(args: &Args) -> Result<ExitCode, String> {
+    let day = args.day()?;
+    let Some(check) = day.check else {
+        println!("day {:02}: no assumption checks", day.number);
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    let warnings = check(&read_input(&day, args)?);
+    if warnings.is_empty() {
+        println!("day {:02}: all assumptions hold", day.number);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    for warning in &warnings {
+        println!("day {:02}: warning: {warning}", day.number);
+    }
+    Ok(ExitCode::FAILURE)
+}
+
+fn query(args: &Args) -> Result<ExitCode, String> {
+    let (day, expr) = args.day_and_query()?;
+    let Some(query) = day.query else {
+        return Err(format!("day {:02} has no query language", day.number));
+    };
+
+    let matches = query(&read_input(&day, args)?, expr)?;
+    for m in &matches {
+        println!("{m}");
+    }
+    eprintln!("{} matches", matches.len());
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn stats(args: &Args) -> Result<ExitCode, String> {
+    let day = args.day()?;
+    let Some(stats) = day.stats else {
+        return Err(format!("day {:02} has no statistics", day.number));
+    };
+
+    let format = match args.option("format") {
+        None | Some("table") => ReportFormat::Table,
+        Some("csv") => ReportFormat::Csv,
+        Some(f) => return Err(format!("unknown format '{f}'")),
+    };
+
+    print!("{}", stats(&read_input(&day, args)?, format)?);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn generate(args: &Args) -> Result<ExitCode, String> {
+    let day = args.day()?;
+    let seed = args.parsed_option("seed")?.unwrap_or(0);
+    let size = args
+        .parsed_option("size")?
+        .ok_or_else(|| "gen needs a --size".to_owned())?;
+
+    let generated = (day.generate)(seed, size);
+
+    if let Some(path) = args.option("output") {
+        fs::write(path, &generated.input).map_err(|e| format!("writing {path}: {e}"))?;
+    } else {
+        // no trailing newline: the parsers expect the input to end on its last line
+        let mut stdout = io::stdout();
+        stdout
+            .write_all(generated.input.as_bytes())
+            .and_then(|()| stdout.flush())
+            .map_err(|e| format!("writing input: {e}"))?;
+    }
+
+    let unknown = || "unknown".to_owned();
+    eprintln!("answer a: {}", generated.answer_a.unwrap_or_else(unknown));
+    eprintln!("answer b: {}", generated.answer_b.unwrap_or_else(unknown));
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,305 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "alloc-profile")]
+pub use counting::Counting;
+
+/// Memory used over a measured call. Heap usage is as seen by the global
+/// allocator, so leaves out memory the allocator holds on to, stacks and the
+/// binary itself; the peak resident set size takes in all of those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Memory {
+    pub allocations: usize,
+    /// Bytes allocated, counting only the growth of reallocations.
+    pub bytes: usize,
+    /// Highest number of live heap bytes above what was live before the call.
+    pub peak_heap: usize,
+    /// Highest resident set size of the whole process during the call. Only
+    /// known on Linux, where the high-water mark can be reset before it.
+    pub peak_rss: Option<usize>,
+}
+
+impl Display for Memory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>9} allocs {:>10} total {:>10} peak heap {:>10} peak rss",
+            self.allocations,
+            human_bytes(self.bytes),
+            human_bytes(self.peak_heap),
+            self.peak_rss.map_or_else(|| "-".to_owned(), human_bytes),
+        )
+    }
+}
+
+pub struct Measurement<T> {
+    pub value: T,
+    pub elapsed: Duration,
+    /// Only recorded when built with the `alloc-profile` feature.
+    pub memory: Option<Memory>,
+}
+
+pub fn measure<T>(f: impl FnOnce() -> T) -> Measurement<T> {
+    #[cfg(feature = "alloc-profile")]
+    let rss_reset = rss::reset_peak();
+    #[cfg(feature = "alloc-profile")]
+    counting::reset();
+
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+
+    #[cfg(feature = "alloc-profile")]
+    let memory = Some(Memory {
+        peak_rss: rss_reset.then(rss::peak).flatten(),
+        ..counting::memory()
+    });
+    #[cfg(not(feature = "alloc-profile"))]
+    let memory = None;
+
+    Measurement {
+        value,
+        elapsed,
+        memory,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn human_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1}{}", UNITS[unit])
+}
+
+#[cfg(feature = "alloc-profile")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::Memory;
+
+    /// The counts shared by every thread of the process.
+    static COUNTERS: Counters = Counters::new();
+
+    /// Wraps the system allocator and counts every allocation made through it.
+    pub struct Counting;
+
+    pub struct Counters {
+        allocations: AtomicUsize,
+        bytes: AtomicUsize,
+        live: AtomicUsize,
+        peak: AtomicUsize,
+        baseline: AtomicUsize,
+    }
+
+    impl Counters {
+        pub const fn new() -> Self {
+            Self {
+                allocations: AtomicUsize::new(0),
+                bytes: AtomicUsize::new(0),
+                live: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+                baseline: AtomicUsize::new(0),
+            }
+        }
+
+        pub fn record_alloc(&self, size: usize) {
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(size, Ordering::Relaxed);
+            let live = self.live.fetch_add(size, Ordering::Relaxed) + size;
+            self.peak.fetch_max(live, Ordering::Relaxed);
+        }
+
+        pub fn record_dealloc(&self, size: usize) {
+            self.live.fetch_sub(size, Ordering::Relaxed);
+        }
+
+        /// A realloc only changes the live bytes by the difference in size, so
+        /// growing a buffer doesn't count both the old and new one at the peak.
+        pub fn record_realloc(&self, old: usize, new: usize) {
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+            if new > old {
+                self.bytes.fetch_add(new - old, Ordering::Relaxed);
+                let live = self.live.fetch_add(new - old, Ordering::Relaxed) + new - old;
+                self.peak.fetch_max(live, Ordering::Relaxed);
+            } else {
+                self.live.fetch_sub(old - new, Ordering::Relaxed);
+            }
+        }
+
+        pub fn reset(&self) {
+            self.allocations.store(0, Ordering::Relaxed);
+            self.bytes.store(0, Ordering::Relaxed);
+            let live = self.live.load(Ordering::Relaxed);
+            self.baseline.store(live, Ordering::Relaxed);
+            self.peak.store(live, Ordering::Relaxed);
+        }
+
+        pub fn memory(&self) -> Memory {
+            Memory {
+                allocations: self.allocations.load(Ordering::Relaxed),
+                bytes: self.bytes.load(Ordering::Relaxed),
+                peak_heap: self
+                    .peak
+                    .load(Ordering::Relaxed)
+                    .saturating_sub(self.baseline.load(Ordering::Relaxed)),
+                peak_rss: None,
+            }
+        }
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                COUNTERS.record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                COUNTERS.record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            COUNTERS.record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                COUNTERS.record_realloc(layout.size(), new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn reset() {
+        COUNTERS.reset();
+    }
+
+    pub fn memory() -> Memory {
+        COUNTERS.memory()
+    }
+}
+
+/// The process's peak resident set size, which Linux keeps as `VmHWM` in
+/// `/proc/self/status` and resets to the current size when `5` is written to
+/// `/proc/self/clear_refs`.
+#[cfg(feature = "alloc-profile")]
+mod rss {
+    /// Starts a new high-water mark, returning whether that worked.
+    #[cfg(target_os = "linux")]
+    pub fn reset_peak() -> bool {
+        std::fs::write("/proc/self/clear_refs", "5").is_ok()
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn peak() -> Option<usize> {
+        vm_hwm(&std::fs::read_to_string("/proc/self/status").ok()?)
+    }
+
+    /// The `VmHWM` line of a `/proc/<pid>/status` file, in bytes.
+    #[cfg(target_os = "linux")]
+    pub fn vm_hwm(status: &str) -> Option<usize> {
+        let kib = status
+            .lines()
+            .find_map(|l| l.strip_prefix("VmHWM:"))?
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse::<usize>()
+            .ok()?;
+        Some(kib * 1024)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn reset_peak() -> bool {
+        false
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn peak() -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_formatting() {
+        assert_eq!(human_bytes(1023), "1023B");
+        assert_eq!(human_bytes(1536), "1.5KiB");
+        assert_eq!(human_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+
+    #[cfg(feature = "alloc-profile")]
+    #[test]
+    fn counts_allocations() {
+        let measurement = measure(|| vec![0_u8; 4096].len());
+        let memory = measurement.memory.unwrap();
+        assert_eq!(measurement.value, 4096);
+        assert!(memory.allocations >= 1);
+        assert!(memory.bytes >= 4096);
+        assert!(memory.peak_heap >= 4096);
+    }
+
+    #[cfg(all(feature = "alloc-profile", target_os = "linux"))]
+    #[test]
+    fn peak_rss() {
+        let status = "Name:\taoc\nVmPeak:\t  10000 kB\nVmHWM:\t    2048 kB\nVmRSS:\t 1024 kB\n";
+        assert_eq!(rss::vm_hwm(status), Some(2048 * 1024));
+        assert_eq!(rss::vm_hwm("VmRSS:\t 1024 kB"), None);
+
+        let measurement = measure(|| {
+            let v = vec![1_u8; 16 << 20];
+            v.iter().map(|&b| usize::from(b)).sum::<usize>()
+        });
+        assert_eq!(measurement.value, 16 << 20);
+        if let Some(peak) = measurement.memory.unwrap().peak_rss {
+            assert!(peak >= 16 << 20);
+        }
+    }
+
+    // the global counts take in other tests' threads, so only lower bounds
+    // hold for them; exact counts use counters of their own
+    #[cfg(feature = "alloc-profile")]
+    #[test]
+    fn realloc_counts_growth() {
+        let counters = counting::Counters::new();
+        counters.record_alloc(4096);
+        counters.record_realloc(4096, 8192);
+        let memory = counters.memory();
+        assert_eq!(memory.allocations, 2);
+        assert_eq!(memory.bytes, 8192);
+        assert_eq!(memory.peak_heap, 8192);
+
+        counters.reset();
+        counters.record_realloc(8192, 1024);
+        counters.record_dealloc(1024);
+        let memory = counters.memory();
+        assert_eq!(memory.bytes, 0);
+        assert_eq!(memory.peak_heap, 0);
+    }
+}
@@ -0,0 +1,272 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Output},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{args::Args, days::Day};
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// The outcome of rebuilding and running a day once.
+#[derive(Debug, Default, PartialEq)]
+struct Run {
+    /// Pass or fail for each test, which cover the examples and real input.
+    tests: BTreeMap<String, bool>,
+    /// Answer and time for each part on the real input.
+    parts: BTreeMap<String, (String, Duration)>,
+    error: Option<String>,
+}
+
+pub fn watch(args: &Args) -> Result<ExitCode, String> {
+    let day = args.day()?;
+    let dir = day.crate_dir();
+
+    let mut files = snapshot(&dir);
+    let mut previous = None;
+    loop {
+        println!("day {:02}: rebuilding", day.number);
+        let run = run_once(&day);
+        print!("{}", report(day.number, previous.as_ref(), &run));
+        previous = Some(run);
+
+        loop {
+            thread::sleep(POLL);
+            let now = snapshot(&dir);
+            if now != files {
+                files = now;
+                break;
+            }
+        }
+    }
+}
+
+/// Modification times of every file in the crate outside `target`.
+fn snapshot(dir: &Path) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if entry.file_name() != "target" {
+                    dirs.push(path);
+                }
+            } else if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                files.insert(path, modified);
+            }
+        }
+    }
+    files
+}
+
+fn run_once(day: &Day) -> Run {
+    let manifest = day.crate_dir().join("Cargo.toml");
+    let tests = match cargo(&["test", "--release", "--manifest-path"], &manifest, &[]) {
+        Ok(output) => parse_tests(&String::from_utf8_lossy(&output.stdout)),
+        Err(error) => {
+            return Run {
+                error: Some(error),
+                ..Run::default()
+            }
+        }
+    };
+
+    let runner = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let number = day.number.to_string();
+    let parts = cargo(
+        &["run", "--release", "--quiet", "--manifest-path"],
+        &runner,
+        &["--", "run", &number, "--format", "tsv"],
+    )
+    .map(|output| parse_parts(&String::from_utf8_lossy(&output.stdout)));
+
+    match parts {
+        Ok(parts) => Run {
+            tests,
+            parts,
+            error: None,
+        },
+        Err(error) => Run {
+            tests,
+            error: Some(error),
+            ..Run::default()
+        },
+    }
+}
+
+/// Runs cargo, treating failing tests as success so their results can be read.
+fn cargo(args: &[&str], manifest: &Path, rest: &[&str]) -> Result<Output, String> {
+    let output = Command::new(env!("CARGO"))
+        .args(args)
+        .arg(manifest)
+        .args(rest)
+        .output()
+        .map_err(|e| format!("running cargo: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() || stdout.contains("test result: FAILED") {
+        return Ok(output);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let errors = stderr
+        .lines()
+        .filter(|l| l.starts_with("error") || l.contains("panicked"))
+        .take(5)
+        .collect::<Vec<_>>();
+    Err(if errors.is_empty() {
+        format!("cargo exited with {}", output.status)
+    } else {
+        errors.join("\n")
+    })
+}
+
+fn parse_tests(stdout: &str) -> BTreeMap<String, bool> {
+    stdout
+        .lines()
+        .filter_map(|l| l.strip_prefix("test "))
+        .filter_map(|l| l.split_once(" ... "))
+        .filter_map(|(name, result)| match result {
+            "ok" => Some((name.to_owned(), true)),
+            "FAILED" => Some((name.to_owned(), false)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_parts(stdout: &str) -> BTreeMap<String, (String, Duration)> {
+    stdout
+        .lines()
+        .filter_map(|l| match l.split('\t').collect::<Vec<_>>()[..] {
+            [_, part, answer, nanos] => Some((
+                part.to_owned(),
+                (answer.to_owned(), Duration::from_nanos(nanos.parse().ok()?)),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn report(day: u8, previous: Option<&Run>, run: &Run) -> String {
+    let mut out = String::new();
+
+    if let Some(error) = &run.error {
+        writeln!(out, "day {day:02}: failed").unwrap();
+        for line in error.lines() {
+            writeln!(out, "  {line}").unwrap();
+        }
+        return out;
+    }
+
+    let passed = run.tests.values().filter(|&&p| p).count();
+    writeln!(
+        out,
+        "day {day:02}: {passed}/{} tests passed",
+        run.tests.len()
+    )
+    .unwrap();
+
+    for (name, &pass) in &run.tests {
+        let before = previous.and_then(|p| p.tests.get(name)).copied();
+        match (before, pass) {
+            (Some(true) | None, false) => writeln!(out, "  FAIL {name}").unwrap(),
+            (Some(false), true) => writeln!(out, "  fixed {name}").unwrap(),
+            _ => {}
+        }
+    }
+
+    for (part, (answer, elapsed)) in &run.parts {
+        write!(out, "  {part}: {answer:>20} {elapsed:>12.2?}").unwrap();
+        if let Some((before_answer, before)) = previous.and_then(|p| p.parts.get(part)) {
+            let change = (elapsed.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+            write!(out, " ({change:+.1}%)").unwrap();
+            if before_answer != answer {
+                write!(out, " was {before_answer}").unwrap();
+            }
+        }
+        writeln!(out).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_OUTPUT: &str = "
+running 3 tests
+test tests::example_a ... ok
+test tests::example_b ... FAILED
+test tests::slow ... ignored
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out";
+
+    fn run(tests: &[(&str, bool)], parts: &[(&str, &str, u64)]) -> Run {
+        Run {
+            tests: tests.iter().map(|&(n, p)| (n.to_owned(), p)).collect(),
+            parts: parts
+                .iter()
+                .map(|&(p, a, ms)| (p.to_owned(), (a.to_owned(), Duration::from_millis(ms))))
+                .collect(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_results() {
+        assert_eq!(
+            parse_tests(TEST_OUTPUT),
+            run(
+                &[("tests::example_a", true), ("tests::example_b", false)],
+                &[]
+            )
+            .tests
+        );
+    }
+
+    #[test]
+    fn part_results() {
+        assert_eq!(
+            parse_parts("8\ta\t17873\t2000000\n8\tb\t42\t1000000\n"),
+            run(&[], &[("a", "17873", 2), ("b", "42", 1)]).parts
+        );
+    }
+
+    #[test]
+    fn first_report() {
+        let current = run(&[("a", true), ("b", false)], &[("a", "1", 2)]);
+        assert_eq!(
+            report(8, None, &current),
+            "day 08: 1/2 tests passed
+  FAIL b
+  a:                    1       2.00ms
+"
+        );
+    }
+
+    #[test]
+    fn report_diff() {
+        let before = run(
+            &[("a", false), ("b", true)],
+            &[("a", "1", 2), ("b", "5", 4)],
+        );
+        let after = run(&[("a", true), ("b", true)], &[("a", "1", 1), ("b", "6", 5)]);
+        assert_eq!(
+            report(8, Some(&before), &after),
+            "day 08: 2/2 tests passed
+  fixed a
+  a:                    1       1.00ms (-50.0%)
+  b:                    6       5.00ms (+25.0%) was 5
+"
+        );
+    }
+}
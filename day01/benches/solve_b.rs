@@ -0,0 +1,49 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day01::{generate, solve_b};
+
+/// `solve_b` as it was before the trie: try every word at every byte and
+/// collect all the digits in each line.
+fn starts_with_scan(input: &str) -> u32 {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    input
+        .lines()
+        .map(|line| {
+            let mut number = vec![];
+            for i in 0..line.len() {
+                let sub_slice = &line[i..];
+
+                if let Some(w) = WORDS.iter().position(|w| sub_slice.starts_with(w)) {
+                    number.push(u32::try_from(w).unwrap() + 1);
+                } else if sub_slice.chars().next().unwrap().is_numeric() {
+                    number.push(sub_slice.chars().next().unwrap().to_digit(10).unwrap());
+                }
+            }
+            number.first().unwrap() * 10 + number.last().unwrap()
+        })
+        .sum()
+}
+
+fn bench_solve_b(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day01 b");
+
+    for size in [1_000, 100_000] {
+        let (input, _, expected) = generate(0, size);
+        assert_eq!(Some(starts_with_scan(&input)), expected);
+        assert_eq!(Some(solve_b(&input)), expected);
+
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("starts_with", size), &input, |b, i| {
+            b.iter(|| starts_with_scan(i));
+        });
+        group.bench_with_input(BenchmarkId::new("trie", size), &input, |b, i| {
+            b.iter(|| solve_b(i));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_solve_b);
+criterion_main!(benches);
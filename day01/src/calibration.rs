@@ -0,0 +1,85 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{Dictionary, Digit};
+
+/// The calibration value of one line, with the digits it was made from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration<'a> {
+    pub first: Digit<'a>,
+    pub last: Digit<'a>,
+}
+
+impl Calibration<'_> {
+    #[must_use]
+    pub fn value(&self) -> u32 {
+        self.first.value * 10 + self.last.value
+    }
+}
+
+/// What to do with a line that has no digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Stop with an error naming the line.
+    Strict,
+    /// Leave the line out of the sum and list it in [`Calibrated::skipped`].
+    Lenient,
+}
+
+/// The calibrations of every line of a document, and their sum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibrated<'a> {
+    pub sum: u32,
+    pub lines: Vec<Calibration<'a>>,
+    /// Line numbers, counting from 1, of the lines with no digits.
+    pub skipped: Vec<usize>,
+}
+
+/// A line with no digits, found in strict mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoDigits {
+    /// Counting from 1.
+    pub line: usize,
+}
+
+impl Display for NoDigits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {} has no digits", self.line)
+    }
+}
+
+impl Error for NoDigits {}
+
+/// The first and last digits of `line`, or `None` if it has none.
+#[must_use]
+pub fn calibrate_line<'a>(line: &'a str, words: &Dictionary) -> Option<Calibration<'a>> {
+    Some(Calibration {
+        first: words.first_digit(line)?,
+        last: words.last_digit(line)?,
+    })
+}
+
+/// Calibrates every line of `input`, reading the words in `words` as digits.
+pub fn calibrate<'a>(
+    input: &'a str,
+    words: &Dictionary,
+    mode: Mode,
+) -> Result<Calibrated<'a>, NoDigits> {
+    let mut calibrated = Calibrated {
+        sum: 0,
+        lines: vec![],
+        skipped: vec![],
+    };
+
+    for (line, text) in (1..).zip(input.lines()) {
+        match (calibrate_line(text, words), mode) {
+            (Some(calibration), _) => {
+                calibrated.sum += calibration.value();
+                calibrated.lines.push(calibration);
+            }
+            (None, Mode::Strict) => return Err(NoDigits { line }),
+            (None, Mode::Lenient) => calibrated.skipped.push(line),
+        }
+    }
+
+    Ok(calibrated)
+}
@@ -0,0 +1,261 @@
+use crate::numerals;
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// A digit read from a line, with the token it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Digit<'a> {
+    pub value: u32,
+    /// The numeral or word the digit was read from, such as `7`, `seven` or
+    /// `twenty-one`. A token for a number above 9 gives one digit per
+    /// decimal place.
+    pub token: &'a str,
+    /// Byte offset of the token in the line.
+    pub position: usize,
+}
+
+/// The words part b reads as digits, alongside the numeric digits themselves.
+///
+/// Words are held in a byte trie, so finding the word at a position costs at
+/// most one step per byte of the longest word, however many words there are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    nodes: Vec<Node>,
+    /// The length in bytes of the longest word.
+    longest: usize,
+    /// Whether a word inside a longer one, such as the `one` in `twenty-one`,
+    /// is skipped. Only numeral mode skips them, so other dictionaries read
+    /// every overlapping word.
+    skip_nested: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    /// The earliest given word ending here.
+    word: Option<Word>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Word {
+    index: usize,
+    number: u32,
+    /// Whether the word is only read with no letter on either side of it.
+    alone: bool,
+}
+
+impl Dictionary {
+    /// Builds a dictionary from words and the digits they spell.
+    ///
+    /// Where more than one word starts at the same place, the one given first
+    /// is used.
+    ///
+    /// # Panics
+    ///
+    /// If a word is empty or spells a number above 9.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, u32)>) -> Self {
+        Self::build(words.into_iter().map(|(word, digit)| {
+            let word = word.into();
+            assert!(digit < 10, "'{word}' spells {digit}, which isn't a digit");
+            (word, digit, false)
+        }))
+    }
+
+    /// Builds a dictionary whose words may spell numbers of any size, which
+    /// give one digit per decimal place, and may have to stand alone.
+    fn build(words: impl IntoIterator<Item = (String, u32, bool)>) -> Self {
+        let mut nodes = vec![Node::default()];
+        let mut longest = 0;
+
+        for (index, (word, number, alone)) in words.into_iter().enumerate() {
+            assert!(!word.is_empty(), "digit words can't be empty");
+            longest = longest.max(word.len());
+
+            let mut node = 0;
+            for &b in word.as_bytes() {
+                node = if let Some(child) = nodes[node].child(b) {
+                    child
+                } else {
+                    nodes.push(Node::default());
+                    let child = nodes.len() - 1;
+                    nodes[node].children.push((b, child));
+                    child
+                };
+            }
+            nodes[node].word.get_or_insert(Word {
+                index,
+                number,
+                alone,
+            });
+        }
+
+        Self {
+            nodes,
+            longest,
+            skip_nested: false,
+        }
+    }
+
+    /// No words, so only numeric digits are read, as in part a.
+    #[must_use]
+    pub fn numeric() -> Self {
+        Self::new::<&str>([])
+    }
+
+    /// The words `one` to `nine`.
+    #[must_use]
+    pub fn english() -> Self {
+        Self::new(ENGLISH)
+    }
+
+    /// Numeral mode: every Unicode decimal digit, Roman numerals up to 3999
+    /// in ASCII capitals such as `XII` where they stand alone, the Unicode Roman numeral characters
+    /// such as `Ⅻ`, and English numbers from `zero` to `ninety-nine`.
+    #[must_use]
+    pub fn numerals() -> Self {
+        Self {
+            skip_nested: true,
+            ..Self::build(numerals::tokens())
+        }
+    }
+
+    /// The word at the start of `text` and the number it spells, if any. A
+    /// word that must stand alone isn't found if a letter follows it.
+    #[must_use]
+    pub fn word_at<'a>(&self, text: &'a str) -> Option<(&'a str, u32)> {
+        self.find(text, false)
+    }
+
+    /// The word at byte `i` of `line`, checking the letters on both sides of
+    /// one that must stand alone.
+    fn word_in<'a>(&self, line: &'a str, i: usize) -> Option<(&'a str, u32)> {
+        let after_letter = line[..i]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphabetic);
+        self.find(&line[i..], after_letter)
+    }
+
+    /// The earliest given word at the start of `text`, passing over those
+    /// that must stand alone but touch a letter.
+    fn find<'a>(&self, text: &'a str, after_letter: bool) -> Option<(&'a str, u32)> {
+        let mut node = &self.nodes[0];
+        let mut found: Option<(usize, Word)> = None;
+
+        for (len, &b) in (1..).zip(text.as_bytes()) {
+            let Some(child) = node.child(b) else {
+                break;
+            };
+            node = &self.nodes[child];
+            let Some(word) = node.word else {
+                continue;
+            };
+            let alone = !after_letter && !text[len..].starts_with(char::is_alphabetic);
+            if (!word.alone || alone) && found.is_none_or(|(_, w)| word.index < w.index) {
+                found = Some((len, word));
+            }
+        }
+
+        found.map(|(len, word)| (&text[..len], word.number))
+    }
+
+    /// The first digit in `line`, numeric or spelled out.
+    #[must_use]
+    pub fn first_digit<'a>(&self, line: &'a str) -> Option<Digit<'a>> {
+        let (token, position, mut number) = line
+            .char_indices()
+            .find_map(|(i, c)| self.token(line, i, c))?;
+        while number >= 10 {
+            number /= 10;
+        }
+        Some(Digit {
+            value: number,
+            token,
+            position,
+        })
+    }
+
+    /// The last digit in `line`, found by scanning back from the end.
+    #[must_use]
+    pub fn last_digit<'a>(&self, line: &'a str) -> Option<Digit<'a>> {
+        let (token, position, number) = line
+            .char_indices()
+            .rev()
+            .filter_map(|(i, c)| self.token(line, i, c))
+            .find(|&(token, i, _)| !self.inside_word(line, i, i + token.len()))?;
+        Some(Digit {
+            value: number % 10,
+            token,
+            position,
+        })
+    }
+
+    /// Every digit in `line` in order, including those of overlapping words.
+    /// In numeral mode a word inside a longer one, such as the `one` in
+    /// `twenty-one`, gives no digits of its own.
+    pub fn digits<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Digit<'a>> + 'a {
+        line.char_indices()
+            .filter_map(|(i, c)| self.token(line, i, c))
+            .filter(|&(token, i, _)| !self.inside_word(line, i, i + token.len()))
+            .flat_map(|(token, position, number)| {
+                number
+                    .to_string()
+                    .chars()
+                    .map(|c| c.to_digit(10).unwrap())
+                    .map(move |value| Digit {
+                        value,
+                        token,
+                        position,
+                    })
+                    .collect::<Vec<_>>()
+            })
+    }
+
+    /// The token starting with `c` at byte `i` of `line`: a word, or else an
+    /// ASCII digit.
+    fn token<'a>(&self, line: &'a str, i: usize, c: char) -> Option<(&'a str, usize, u32)> {
+        if let Some((word, number)) = self.word_in(line, i) {
+            return Some((word, i, number));
+        }
+        let digit = c.to_digit(10)?;
+        Some((&line[i..=i], i, digit))
+    }
+
+    /// Whether nested words are skipped and a word starting before byte
+    /// `start` of `line` runs to `end` or beyond, so covers the token between
+    /// them.
+    fn inside_word(&self, line: &str, start: usize, end: usize) -> bool {
+        self.skip_nested
+            && (start.saturating_sub(self.longest)..start)
+                .filter(|&j| line.is_char_boundary(j))
+                .any(|j| {
+                    self.word_in(line, j)
+                        .is_some_and(|(word, _)| j + word.len() >= end)
+                })
+    }
+}
+
+impl Node {
+    fn child(&self, b: u8) -> Option<usize> {
+        self.children
+            .iter()
+            .find(|&&(c, _)| c == b)
+            .map(|&(_, child)| child)
+    }
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::english()
+    }
+}
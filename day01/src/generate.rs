@@ -0,0 +1,64 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// none of these letters appear in a digit word, so filler can't create one
+const FILLER: &[u8] = b"abcdjklmpqyz";
+
+/// Generates `size` calibration lines, with the answers to both parts.
+///
+/// Digit words are always separated by filler, so no overlapping words like
+/// `twone` appear by accident.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<u32>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut lines = vec![];
+    let mut sum_a = 0;
+    let mut sum_b = 0;
+
+    for _ in 0..size {
+        // every line needs at least one numeric digit for part a
+        let mut tokens = vec![(rng.gen_range(1..=9), true)];
+        for _ in 0..rng.gen_range(0..6) {
+            tokens.push((rng.gen_range(1..=9), rng.gen_bool(0.5)));
+        }
+        tokens.shuffle(&mut rng);
+
+        let mut line = String::new();
+        for &(digit, numeric) in &tokens {
+            for _ in 0..rng.gen_range(1..4) {
+                line.push(char::from(*FILLER.choose(&mut rng).unwrap()));
+            }
+            if numeric {
+                line.push(char::from_digit(digit, 10).unwrap());
+            } else {
+                line.push_str(WORDS[digit as usize - 1]);
+            }
+        }
+
+        let (first, _) = tokens.iter().find(|&&(_, n)| n).unwrap();
+        let (last, _) = tokens.iter().rfind(|&&(_, n)| n).unwrap();
+        sum_a += first * 10 + last;
+        sum_b += tokens.first().unwrap().0 * 10 + tokens.last().unwrap().0;
+
+        lines.push(line);
+    }
+
+    (lines.join("\n"), Some(sum_a), Some(sum_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 200);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+}
@@ -4,58 +4,39 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod calibration;
+mod dictionary;
+mod generate;
+mod numerals;
+
+pub use calibration::{calibrate, calibrate_line, Calibrated, Calibration, Mode, NoDigits};
+pub use dictionary::{Dictionary, Digit};
+pub use generate::generate;
+
 #[must_use]
 pub fn solve_a(input: &str) -> u32 {
-    let lines = input
-        .lines()
-        .map(|l| l.chars().filter_map(|c| c.to_digit(10)).collect());
-
-    sum(lines)
+    calibrate(input, &Dictionary::numeric(), Mode::Strict)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .sum
 }
 
 #[must_use]
 pub fn solve_b(input: &str) -> u32 {
-    let lines = input.lines().map(|line| {
-        let mut number = vec![];
-        for i in 0..line.len() {
-            let sub_slice = &line[i..];
-
-            if sub_slice.starts_with("one") {
-                number.push(1);
-            } else if sub_slice.starts_with("two") {
-                number.push(2);
-            } else if sub_slice.starts_with("three") {
-                number.push(3);
-            } else if sub_slice.starts_with("four") {
-                number.push(4);
-            } else if sub_slice.starts_with("five") {
-                number.push(5);
-            } else if sub_slice.starts_with("six") {
-                number.push(6);
-            } else if sub_slice.starts_with("seven") {
-                number.push(7);
-            } else if sub_slice.starts_with("eight") {
-                number.push(8);
-            } else if sub_slice.starts_with("nine") {
-                number.push(9);
-            } else if sub_slice.chars().next().unwrap().is_numeric() {
-                number.push(sub_slice.chars().next().unwrap().to_digit(10).unwrap());
-            }
-        }
-        number
-    });
-
-    sum(lines)
+    solve_b_with(input, &Dictionary::english())
 }
 
-fn sum(lines: impl Iterator<Item = Vec<u32>>) -> u32 {
-    lines
-        .map(|l| {
-            let first = *l.first().unwrap();
-            let last = *l.last().unwrap();
-            first * 10 + last
-        })
-        .sum()
+/// Solves part b, reading the words in `words` as digits.
+///
+/// Words may overlap, so `twone` is read as 2 then 1.
+///
+/// # Panics
+///
+/// If a line has no digits.
+#[must_use]
+pub fn solve_b_with(input: &str, words: &Dictionary) -> u32 {
+    calibrate(input, words, Mode::Strict)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .sum
 }
 
 #[cfg(test)]
@@ -102,6 +83,163 @@ zoneight234
         assert_eq!(solve_b(input), 11);
     }
 
+    #[test]
+    fn other_language() {
+        let french = Dictionary::new([
+            ("un", 1),
+            ("deux", 2),
+            ("trois", 3),
+            ("quatre", 4),
+            ("cinq", 5),
+            ("six", 6),
+            ("sept", 7),
+            ("huit", 8),
+            ("neuf", 9),
+        ]);
+        let input = "deuxtrois
+xquatre7
+one2neuf";
+
+        assert_eq!(solve_b_with(input, &french), 23 + 47 + 29);
+    }
+
+    #[test]
+    fn extra_words() {
+        let words = Dictionary::new([("thr", 3), ("one", 1)]);
+        let input = "xthrone
+5three";
+
+        assert_eq!(solve_b_with(input, &words), 31 + 53);
+    }
+
+    #[test]
+    fn first_word_wins() {
+        let words = Dictionary::new([("seven", 7), ("se", 5)]);
+
+        assert_eq!(solve_b_with("seven", &words), 77);
+        assert_eq!(solve_b_with("sex", &words), 55);
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let seven = Dictionary::new([("seven", 7), ("even", 8)]);
+        assert_eq!(solve_b_with("seven", &seven), 78);
+
+        let twone = Dictionary::new([("twone", 2), ("one", 1)]);
+        assert_eq!(solve_b_with("twone", &twone), 21);
+        assert_eq!(
+            twone.digits("twone").map(|d| d.token).collect::<Vec<_>>(),
+            vec!["twone", "one"]
+        );
+    }
+
+    #[test]
+    fn unicode_numerals() {
+        let input = "\u{663}abc\u{667}
+x\u{ff18}y
+Ⅻ
+twenty-one
+seventy
+a XIV-b
+MCMXCIV";
+
+        assert_eq!(
+            solve_b_with(input, &Dictionary::numerals()),
+            37 + 88 + 12 + 21 + 70 + 14 + 14
+        );
+    }
+
+    #[test]
+    fn roman_numerals_stand_alone() {
+        let numerals = Dictionary::numerals();
+
+        assert_eq!(solve_b_with("Mix 1", &numerals), 11);
+        assert_eq!(solve_b_with("aXIVb 2", &numerals), 22);
+        assert_eq!(solve_b_with("MMMM 3", &numerals), 33);
+        assert_eq!(solve_b_with("7,IV.", &numerals), 74);
+        assert_eq!(solve_b_with("Ⅻbc", &numerals), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "'ten' spells 10, which isn't a digit")]
+    fn words_spell_digits() {
+        let _ = Dictionary::new([("ten", 10)]);
+    }
+
+    #[test]
+    fn numerals_ignored_by_default() {
+        assert_eq!(solve_b("\u{663}one\u{667}Ⅻ"), 11);
+    }
+
+    #[test]
+    fn digit_tokens() {
+        let numerals = Dictionary::numerals();
+        let digits = numerals
+            .digits("twenty-oneⅫ\u{663}")
+            .map(|d| (d.value, d.token, d.position))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            digits,
+            vec![
+                (2, "twenty-one", 0),
+                (1, "twenty-one", 0),
+                (1, "Ⅻ", 10),
+                (2, "Ⅻ", 10),
+                (3, "\u{663}", 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_details() {
+        let calibration = calibrate_line("xtwone3four", &Dictionary::english()).unwrap();
+
+        assert_eq!(calibration.value(), 24);
+        assert_eq!(
+            calibration.first,
+            Digit {
+                value: 2,
+                token: "two",
+                position: 1
+            }
+        );
+        assert_eq!(
+            calibration.last,
+            Digit {
+                value: 4,
+                token: "four",
+                position: 7
+            }
+        );
+        assert_eq!(calibrate_line("abc", &Dictionary::english()), None);
+    }
+
+    #[test]
+    fn strict() {
+        let input = "1abc2
+nothing
+three";
+
+        assert_eq!(
+            calibrate(input, &Dictionary::english(), Mode::Strict),
+            Err(NoDigits { line: 2 })
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        let input = "1abc2
+nothing
+three
+";
+        let calibrated = calibrate(input, &Dictionary::english(), Mode::Lenient).unwrap();
+
+        assert_eq!(calibrated.sum, 12 + 33);
+        assert_eq!(calibrated.lines.len(), 2);
+        assert_eq!(calibrated.skipped, vec![2]);
+    }
+
     #[test]
     fn solution_a() {
         assert_eq!(solve_a(include_str!("input.txt")), 56397);
@@ -0,0 +1,181 @@
+//! The tokens read in numeral mode, beyond the English words `one` to `nine`.
+
+/// The zero of each run of ten Unicode decimal digits (general category `Nd`)
+/// other than ASCII, which is always read.
+const DECIMAL_ZEROS: [u32; 76] = [
+    0x660, 0x6F0, 0x7C0, 0x966, 0x9E6, 0xA66, 0xAE6, 0xB66, 0xBE6, 0xC66, 0xCE6, 0xD66, 0xDE6,
+    0xE50, 0xED0, 0xF20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90, 0x1B50,
+    0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50, 0x11D50,
+    0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0, 0x1D7CE,
+    0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950, 0x1FBF0,
+];
+
+/// Each Unicode Roman numeral character, upper then lower case, and its
+/// value.
+const ROMAN: [(char, u32); 36] = [
+    ('Ⅰ', 1),
+    ('Ⅱ', 2),
+    ('Ⅲ', 3),
+    ('Ⅳ', 4),
+    ('Ⅴ', 5),
+    ('Ⅵ', 6),
+    ('Ⅶ', 7),
+    ('Ⅷ', 8),
+    ('Ⅸ', 9),
+    ('Ⅹ', 10),
+    ('Ⅺ', 11),
+    ('Ⅻ', 12),
+    ('Ⅼ', 50),
+    ('Ⅽ', 100),
+    ('Ⅾ', 500),
+    ('Ⅿ', 1000),
+    ('ⅰ', 1),
+    ('ⅱ', 2),
+    ('ⅲ', 3),
+    ('ⅳ', 4),
+    ('ⅴ', 5),
+    ('ⅵ', 6),
+    ('ⅶ', 7),
+    ('ⅷ', 8),
+    ('ⅸ', 9),
+    ('ⅹ', 10),
+    ('ⅺ', 11),
+    ('ⅻ', 12),
+    ('ⅼ', 50),
+    ('ⅽ', 100),
+    ('ⅾ', 500),
+    ('ⅿ', 1000),
+    ('ↀ', 1000),
+    ('ↁ', 5000),
+    ('ↂ', 10000),
+    ('ↅ', 6),
+];
+
+const UNITS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The ASCII Roman numeral letters, largest first, with the pairs written
+/// before a larger letter to subtract from it.
+const ROMAN_ASCII: [(&str, u32); 13] = [
+    ("M", 1000),
+    ("CM", 900),
+    ("D", 500),
+    ("CD", 400),
+    ("C", 100),
+    ("XC", 90),
+    ("L", 50),
+    ("XL", 40),
+    ("X", 10),
+    ("IX", 9),
+    ("V", 5),
+    ("IV", 4),
+    ("I", 1),
+];
+
+/// `n` in ASCII capital Roman numerals, such as `XII` for 12.
+fn roman(mut n: u32) -> String {
+    let mut out = String::new();
+    for (letters, value) in ROMAN_ASCII {
+        while n >= value {
+            out.push_str(letters);
+            n -= value;
+        }
+    }
+    out
+}
+
+/// Every numeral token, the number it stands for and whether it must stand
+/// alone, longest words first so `seventy` is read as 70 rather than 7 and
+/// `XII` as 12 rather than 10.
+///
+/// ASCII Roman numerals must stand alone, with no letter on either side, so
+/// the `M` in `Mix` isn't read as 1000.
+pub fn tokens() -> Vec<(String, u32, bool)> {
+    let mut tokens = vec![];
+
+    for (tens, &word) in (20..).step_by(10).zip(&TENS) {
+        for (units, unit) in (1..).zip(&UNITS[1..]) {
+            tokens.push((format!("{word}-{unit}"), tens + units));
+        }
+    }
+    tokens.extend((20..).step_by(10).zip(TENS).map(|(n, w)| (w.to_owned(), n)));
+    tokens.extend((10..).zip(TEENS).map(|(n, w)| (w.to_owned(), n)));
+    tokens.extend((0..).zip(UNITS).map(|(n, w)| (w.to_owned(), n)));
+
+    for zero in DECIMAL_ZEROS {
+        for digit in 0..10 {
+            let c = char::from_u32(zero + digit).unwrap();
+            tokens.push((c.to_string(), digit));
+        }
+    }
+    tokens.extend(ROMAN.map(|(c, n)| (c.to_string(), n)));
+
+    let mut romans = (1..4000).map(|n| (roman(n), n)).collect::<Vec<_>>();
+    romans.sort_by_key(|(r, _)| std::cmp::Reverse(r.len()));
+
+    tokens
+        .into_iter()
+        .map(|(token, n)| (token, n, false))
+        .chain(romans.into_iter().map(|(token, n)| (token, n, true)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use finl_unicode::categories::CharacterCategories;
+
+    use super::*;
+
+    #[test]
+    fn decimal_zeros_start_runs_of_ten() {
+        let decimal =
+            |c: u32| char::from_u32(c).is_some_and(CharacterCategories::is_number_decimal);
+        for zero in DECIMAL_ZEROS {
+            for digit in 0..10 {
+                assert!(decimal(zero + digit), "{:#x}", zero + digit);
+            }
+            // runs of ten can sit back to back, but a zero never ends one
+            assert!(
+                !decimal(zero - 1) || DECIMAL_ZEROS.contains(&(zero - 10)),
+                "{zero:#x} isn't a zero"
+            );
+        }
+
+        let listed = (0..=0x0010_FFFF)
+            .filter(|&c| decimal(c) && !char::from_u32(c).unwrap().is_ascii_digit())
+            .filter(|c| {
+                !DECIMAL_ZEROS
+                    .iter()
+                    .any(|zero| (zero..&(zero + 10)).contains(&c))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(listed, vec![], "decimal digits missing from the table");
+    }
+
+    #[test]
+    fn ascii_roman() {
+        assert_eq!(roman(12), "XII");
+        assert_eq!(roman(1994), "MCMXCIV");
+        assert_eq!(roman(3999), "MMMCMXCIX");
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::{all_consuming, opt},
+    multi::{fold_many1, separated_list1},
+    sequence::{delimited, pair, preceded, terminated},
+    IResult,
+};
+
+/// A number of cubes of each colour, for a round or a bag. Colours not listed
+/// have none.
+pub type Cubes = BTreeMap<String, u32>;
+
+/// The cubes shown in one round of a game.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Round {
+    pub cubes: Cubes,
+}
+
+/// A game and the rounds it was played in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+/// Every game in an input, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Games {
+    pub games: Vec<Game>,
+}
+
+/// A game that couldn't have been played with a bag, and the rounds that show
+/// more cubes than it holds, counting from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    pub game: &'a Game,
+    pub rounds: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGameError(pub String);
+
+impl Display for ParseGameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid game '{}'", self.0)
+    }
+}
+
+impl Error for ParseGameError {}
+
+/// Whether `c` can be part of a colour name, which is made of letters, digits
+/// and underscores, such as `dark_blue`.
+pub(crate) fn is_colour_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+pub(crate) fn read_game(input: &str) -> Result<Game, ParseGameError> {
+    let rounds: IResult<_, _> = {
+        let parse_round = fold_many1(
+            terminated(
+                pair(
+                    nom::character::complete::u32,
+                    preceded(tag(" "), take_while1(is_colour_char)),
+                ),
+                opt(tag(", ")),
+            ),
+            Round::default,
+            |mut r, (count, colour): (u32, &str)| {
+                *r.cubes.entry(colour.to_owned()).or_default() += count;
+                r
+            },
+        );
+
+        all_consuming(pair(
+            delimited(tag("Game "), nom::character::complete::u32, tag(": ")),
+            separated_list1(tag("; "), parse_round),
+        ))(input)
+    };
+
+    let (_, (id, rounds)) = rounds.map_err(|_| ParseGameError(input.to_owned()))?;
+
+    Ok(Game { id, rounds })
+}
+
+impl Round {
+    /// The number of cubes of `colour` shown.
+    #[must_use]
+    pub fn count(&self, colour: &str) -> u32 {
+        self.cubes.get(colour).copied().unwrap_or(0)
+    }
+
+    /// Whether `bag` holds at least as many cubes of each colour as shown.
+    #[must_use]
+    pub fn fits(&self, bag: &Cubes) -> bool {
+        self.cubes
+            .iter()
+            .all(|(colour, &count)| count <= bag.get(colour).copied().unwrap_or(0))
+    }
+}
+
+impl Game {
+    #[must_use]
+    pub fn is_possible_with(&self, bag: &Cubes) -> bool {
+        self.rounds.iter().all(|round| round.fits(bag))
+    }
+
+    /// The fewest cubes of each colour the game could have been played with.
+    #[must_use]
+    pub fn minimum_bag(&self) -> Cubes {
+        let mut bag = Cubes::new();
+        for (colour, &count) in self.rounds.iter().flat_map(|r| &r.cubes) {
+            let most = bag.entry(colour.clone()).or_default();
+            *most = (*most).max(count);
+        }
+        bag
+    }
+
+    /// The product of the counts in the minimum bag. Only the colours the
+    /// game shows are counted.
+    #[must_use]
+    pub fn power(&self) -> u32 {
+        self.minimum_bag().values().product()
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, round) in self.rounds.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            for (j, (colour, count)) in round.cubes.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{count} {colour}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        read_game(s)
+    }
+}
+
+impl Games {
+    /// Every colour shown in any game.
+    #[must_use]
+    pub fn colours(&self) -> BTreeSet<&str> {
+        self.games
+            .iter()
+            .flat_map(|g| &g.rounds)
+            .flat_map(|r| r.cubes.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn possible_with<'a>(&'a self, bag: &'a Cubes) -> impl Iterator<Item = &'a Game> + 'a {
+        self.games.iter().filter(|g| g.is_possible_with(bag))
+    }
+
+    /// The games that couldn't have been played with `bag`, with the rounds
+    /// that rule each one out.
+    #[must_use]
+    pub fn failures<'a>(&'a self, bag: &Cubes) -> Vec<Failure<'a>> {
+        self.games
+            .iter()
+            .filter_map(|game| {
+                let rounds = (0..)
+                    .zip(&game.rounds)
+                    .filter(|(_, round)| !round.fits(bag))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                (!rounds.is_empty()).then_some(Failure { game, rounds })
+            })
+            .collect()
+    }
+
+    /// Every game's power, taken over all the colours in the input so a game
+    /// that never shows one of them has a power of 0.
+    pub fn powers(&self) -> impl Iterator<Item = u32> + '_ {
+        let colours = self.colours();
+        self.games.iter().map(move |game| {
+            let bag = game.minimum_bag();
+            if colours.iter().all(|&c| bag.contains_key(c)) {
+                game.power()
+            } else {
+                0
+            }
+        })
+    }
+
+    /// The sum of every game's [power](Games::powers).
+    #[must_use]
+    pub fn power_sum(&self) -> u32 {
+        self.powers().sum()
+    }
+}
+
+impl FromStr for Games {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let games = s.lines().map(read_game).collect::<Result<_, _>>()?;
+        Ok(Self { games })
+    }
+}
@@ -0,0 +1,58 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// Generates `size` games, with the answers to both parts.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<u32>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut lines = vec![];
+    let mut sum_a = 0;
+    let mut sum_b = 0;
+
+    for id in 1..=u32::try_from(size).unwrap() {
+        let mut max = [0; 3];
+
+        let rounds = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let mut colours = [0, 1, 2];
+                colours.shuffle(&mut rng);
+                let len = rng.gen_range(1..=3);
+
+                colours[..len]
+                    .iter()
+                    .map(|&c| {
+                        let count = rng.gen_range(1..=20);
+                        max[c] = max[c].max(count);
+                        format!("{count} {}", COLOURS[c])
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if max[0] <= 12 && max[1] <= 13 && max[2] <= 14 {
+            sum_a += id;
+        }
+        sum_b += max.iter().product::<u32>();
+
+        lines.push(format!("Game {id}: {rounds}"));
+    }
+
+    (lines.join("\n"), Some(sum_a), Some(sum_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 200);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Cubes, Game};
+
+impl Game {
+    /// The natural log of the chance of seeing this game's rounds if each was
+    /// drawn without replacement from `bag`, with the cubes put back between
+    /// rounds. Negative infinity if a round shows more cubes than `bag` holds.
+    #[must_use]
+    pub fn log_likelihood(&self, bag: &Cubes) -> f64 {
+        let total = bag.values().sum();
+
+        self.rounds
+            .iter()
+            .map(|round| {
+                if !round.fits(bag) {
+                    return f64::NEG_INFINITY;
+                }
+                let drawn = round.cubes.values().sum();
+                round
+                    .cubes
+                    .iter()
+                    .map(|(colour, &count)| ln_choose(bag.get(colour).copied().unwrap_or(0), count))
+                    .sum::<f64>()
+                    - ln_choose(total, drawn)
+            })
+            .sum()
+    }
+
+    /// The chance of seeing this game's rounds if they were drawn from `bag`.
+    /// See [`Game::log_likelihood`].
+    #[must_use]
+    pub fn likelihood(&self, bag: &Cubes) -> f64 {
+        self.log_likelihood(bag).exp()
+    }
+
+    /// The bag most likely to have produced this game's rounds, holding at
+    /// most `cap` cubes of any colour, or more where a round shows more.
+    ///
+    /// Every bag between [`Game::minimum_bag`] and `cap` is tried, so this
+    /// takes time exponential in the number of colours. The likelihood can
+    /// keep rising as a bag grows in the proportions the rounds show, so the
+    /// best bag may be at the cap. Of equally likely bags, the smallest is
+    /// returned.
+    #[must_use]
+    pub fn most_likely_bag(&self, cap: u32) -> Cubes {
+        let minimum = self.minimum_bag();
+        let mut bag = minimum.clone();
+        let mut best = (self.log_likelihood(&bag), bag.values().sum::<u32>());
+        let mut best_bag = bag.clone();
+
+        loop {
+            // step to the next bag, like an odometer
+            let mut colours = minimum.iter();
+            loop {
+                let Some((colour, &least)) = colours.next() else {
+                    return best_bag;
+                };
+                let count = bag.get_mut(colour).unwrap();
+                if *count < cap {
+                    *count += 1;
+                    break;
+                }
+                *count = least;
+            }
+
+            let score = self.log_likelihood(&bag);
+            let total = bag.values().sum();
+            let (best_score, best_total) = best;
+            if score > best_score + 1e-9 || (score > best_score - 1e-9 && total < best_total) {
+                best = (score, total);
+                best_bag.clone_from(&bag);
+            }
+        }
+    }
+}
+
+fn ln_choose(n: u32, k: u32) -> f64 {
+    let k = k.min(n - k);
+    (1..=k)
+        .map(|i| (f64::from(n - k + i) / f64::from(i)).ln())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn bag(red: u32, green: u32, blue: u32) -> Cubes {
+        Cubes::from([
+            ("red".to_owned(), red),
+            ("green".to_owned(), green),
+            ("blue".to_owned(), blue),
+        ])
+    }
+
+    #[test]
+    fn likelihood_of_minimum_bag() {
+        let game: Game = EXAMPLE.lines().next().unwrap().parse().unwrap();
+
+        let expected = 20.0 / 792.0 * 4.0 / 220.0 * 1.0 / 66.0;
+        assert!((game.likelihood(&bag(4, 2, 6)) - expected).abs() < 1e-12);
+        assert!(game.likelihood(&bag(3, 2, 6)) == 0.0);
+    }
+
+    #[test]
+    fn most_likely_is_best() {
+        const CAP: u32 = 25;
+
+        for line in EXAMPLE.lines() {
+            let game: Game = line.parse().unwrap();
+            let found = game.most_likely_bag(CAP);
+            let best = game.log_likelihood(&found);
+
+            for red in 0..=CAP {
+                for green in 0..=CAP {
+                    for blue in 0..=CAP {
+                        assert!(
+                            game.log_likelihood(&bag(red, green, blue)) <= best + 1e-9,
+                            "game {}: {red} {green} {blue} beats {found:?}",
+                            game.id
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    // an impossible round makes the likelihood exactly 0, not just small
+    #[allow(clippy::float_cmp)]
+    fn standard_bag_plausibility() {
+        let games: crate::Games = EXAMPLE.parse().unwrap();
+        let standard = crate::standard_bag();
+
+        assert_eq!(games.games[2].likelihood(&standard), 0.0);
+        assert!(games.games[0].likelihood(&standard) > 0.0);
+    }
+}
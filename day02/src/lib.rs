@@ -4,105 +4,57 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    combinator::{all_consuming, opt, value},
-    multi::{fold_many1, separated_list1},
-    sequence::{delimited, pair, terminated},
-    IResult,
-};
-
-#[derive(Debug, Default)]
-struct CountSummary {
-    red: u32,
-    green: u32,
-    blue: u32,
+mod game;
+mod generate;
+mod inference;
+mod query;
+mod stats;
+
+pub use game::{Cubes, Failure, Game, Games, ParseGameError, Round};
+pub use generate::generate;
+pub use query::{Query, QueryError};
+pub use stats::{ColourStats, Margin, Stats};
+
+/// The bag part a asks about: 12 red, 13 green and 14 blue cubes.
+#[must_use]
+pub fn standard_bag() -> Cubes {
+    [("red", 12), ("green", 13), ("blue", 14)]
+        .into_iter()
+        .map(|(colour, count)| (colour.to_owned(), count))
+        .collect()
 }
 
-fn read_game(input: &str) -> (u32, Vec<CountSummary>) {
-    #[derive(Clone, Copy, Debug)]
-    enum CubeColors {
-        Red,
-        Green,
-        Blue,
-    }
-
-    let rounds: IResult<_, _> = {
-        let parse_round = fold_many1(
-            terminated(
-                pair(
-                    nom::character::complete::u32,
-                    alt((
-                        value(CubeColors::Red, tag(" red")),
-                        value(CubeColors::Green, tag(" green")),
-                        value(CubeColors::Blue, tag(" blue")),
-                    )),
-                ),
-                opt(tag(", ")),
-            ),
-            CountSummary::default,
-            |mut r, (count, color)| {
-                match color {
-                    CubeColors::Red => r.red += count,
-                    CubeColors::Green => r.green += count,
-                    CubeColors::Blue => r.blue += count,
-                };
-                r
-            },
-        );
-
-        all_consuming(pair(
-            delimited(tag("Game "), nom::character::complete::u32, tag(": ")),
-            separated_list1(tag("; "), parse_round),
-        ))(input)
-    };
-
-    let (_, (id, rounds)) = rounds.unwrap();
-
-    (id, rounds)
+#[must_use]
+pub fn solve_a(input: &str) -> u32 {
+    solve_a_with(input, &standard_bag())
 }
 
+/// Sums the ids of the games that could have been played with `bag`.
 #[must_use]
-pub fn solve_a(input: &str) -> u32 {
-    let target = CountSummary {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-
-    input
-        .lines()
-        .map(read_game)
-        .filter_map(|(id, rounds)| {
-            rounds
-                .into_iter()
-                .all(|round| {
-                    round.red <= target.red
-                        && round.green <= target.green
-                        && round.blue <= target.blue
-                })
-                .then_some(id)
-        })
-        .sum()
+pub fn solve_a_with(input: &str, bag: &Cubes) -> u32 {
+    let games: Games = input.parse().unwrap();
+    games.possible_with(bag).map(|g| g.id).sum()
 }
 
+/// Sums the power of each game's smallest possible bag.
+///
+/// The power multiplies together the counts of every colour seen anywhere in
+/// the input, so a game that never shows one of them has a power of 0.
 #[must_use]
 pub fn solve_b(input: &str) -> u32 {
-    input
-        .lines()
-        .map(read_game)
-        .map(|(_, rounds)| {
-            let max = rounds
-                .into_iter()
-                .fold(CountSummary::default(), |c, round| CountSummary {
-                    red: c.red.max(round.red),
-                    green: c.green.max(round.green),
-                    blue: c.blue.max(round.blue),
-                });
-            max.red * max.green * max.blue
-        })
-        .sum()
+    let games: Games = input.parse().unwrap();
+    games.power_sum()
+}
+
+/// The games in `input` that match the query `expr`.
+pub fn query(input: &str, expr: &str) -> Result<Vec<Game>, QueryError> {
+    let query: Query = expr.parse()?;
+    let games: Games = input.parse()?;
+    Ok(games
+        .games
+        .into_iter()
+        .filter(|g| query.matches(g))
+        .collect())
 }
 
 #[cfg(test)]
@@ -125,6 +77,76 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         assert_eq!(solve_b(EXAMPLE), 2286);
     }
 
+    #[test]
+    fn other_colours() {
+        let input = "Game 1: 3 yellow, 4 red; 1 red, 2 green, 6 blue
+Game 2: 1 blue, 2 green; 3 green, 5 yellow, 1 red
+Game 3: 2 red, 2 green, 2 blue";
+        let mut bag = standard_bag();
+        bag.insert("yellow".to_owned(), 4);
+
+        assert_eq!(solve_a_with(input, &bag), 1 + 3);
+        assert_eq!(solve_a_with(input, &standard_bag()), 3);
+        // game 3 shows no yellow, so its power is 0
+        assert_eq!(solve_b(input), 6 * 2 * 4 * 3 + 3 * 5);
+    }
+
+    #[test]
+    fn game_queries() {
+        let game: Game = EXAMPLE.lines().nth(2).unwrap().parse().unwrap();
+
+        assert_eq!(game.id, 3);
+        assert_eq!(game.rounds[0].count("red"), 20);
+        assert!(!game.is_possible_with(&standard_bag()));
+        assert_eq!(
+            game.minimum_bag(),
+            Cubes::from([
+                ("red".to_owned(), 20),
+                ("green".to_owned(), 13),
+                ("blue".to_owned(), 6)
+            ])
+        );
+        assert_eq!(game.power(), 1560);
+    }
+
+    #[test]
+    fn failing_games() {
+        let games: Games = EXAMPLE.parse().unwrap();
+        let failures = games
+            .failures(&standard_bag())
+            .into_iter()
+            .map(|f| (f.game.id, f.rounds))
+            .collect::<Vec<_>>();
+
+        assert_eq!(failures, vec![(3, vec![0]), (4, vec![2])]);
+    }
+
+    #[test]
+    fn invalid_game() {
+        assert_eq!(
+            "Game 1: 3 blue,".parse::<Game>(),
+            Err(ParseGameError("Game 1: 3 blue,".to_owned()))
+        );
+    }
+
+    #[test]
+    fn display_game() {
+        let game: Game = "Game 7: 3 blue, 4 red; 2 green".parse().unwrap();
+        assert_eq!(game.to_string(), "Game 7: 3 blue, 4 red; 2 green");
+    }
+
+    #[test]
+    fn query_errors() {
+        assert_eq!(
+            query("Game 1: 3 blue\nGame 2", "id = 1"),
+            Err(QueryError::Input(ParseGameError("Game 2".to_owned())))
+        );
+        assert!(matches!(
+            query(EXAMPLE, "red >"),
+            Err(QueryError::Invalid(_))
+        ));
+    }
+
     #[test]
     fn solution_a() {
         assert_eq!(solve_a(include_str!("input.txt")), 2101);
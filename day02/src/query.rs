@@ -0,0 +1,338 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use winnow::{
+    ascii::{dec_uint, multispace0},
+    combinator::{alt, delimited, preceded, separated},
+    error::ContextError,
+    token::take_while,
+    PResult, Parser,
+};
+
+use crate::{game::is_colour_char, Game, Games, ParseGameError, Round};
+
+const KEYWORDS: [&str; 11] = [
+    "and", "or", "not", "any", "every", "round", "id", "rounds", "max", "min", "sum",
+];
+
+/// A condition on a game, such as `any round (red > 10) and max(blue) <= 3`.
+///
+/// ```text
+/// query      := and ("or" and)*
+/// and        := not ("and" not)*
+/// not        := "not" not | "(" query ")" | quantified | comparison
+/// quantified := ("any" | "every") "round" not
+/// comparison := value ("<" | "<=" | ">" | ">=" | "=" | "!=") value
+/// value      := number | colour | ("max" | "min" | "sum") "(" colour ")"
+///             | "id" | "rounds"
+/// ```
+///
+/// A bare colour is the count shown in a round, so it can only be used inside
+/// `any round` or `every round`. The aggregates are taken over the whole game.
+/// Colours are named as in the games, with letters, digits and underscores.
+///
+/// Queries are only made by parsing, which checks where colours are counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query(Expr);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+    Not(Box<Self>),
+    AnyRound(Box<Self>),
+    EveryRound(Box<Self>),
+    Compare(Value, Comparison, Value),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Number(u32),
+    /// The count of a colour in the current round.
+    Count(String),
+    Aggregate(Aggregate, String),
+    Id,
+    /// The number of rounds in the game.
+    Rounds,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Aggregate {
+    Max,
+    Min,
+    Sum,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// The query doesn't follow the grammar, or counts a colour outside a
+    /// round.
+    Invalid(String),
+    /// The games to query couldn't be read.
+    Input(ParseGameError),
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(message) => f.write_str(message),
+            Self::Input(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<ParseGameError> for QueryError {
+    fn from(e: ParseGameError) -> Self {
+        Self::Input(e)
+    }
+}
+
+impl Error for QueryError {}
+
+fn ws<'a, O>(
+    inner: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, O, ContextError> {
+    delimited(multispace0, inner, multispace0)
+}
+
+/// A keyword or colour name.
+fn word<'a>() -> impl Parser<&'a str, &'a str, ContextError> {
+    ws(take_while(1.., is_colour_char))
+}
+
+fn keyword<'a>(keyword: &'static str) -> impl Parser<&'a str, &'a str, ContextError> {
+    word().verify(move |w: &str| w == keyword)
+}
+
+fn parse_query(input: &mut &str) -> PResult<Expr> {
+    separated(1.., parse_and, keyword("or"))
+        .map(|qs: Vec<_>| {
+            qs.into_iter()
+                .reduce(|a, b| Expr::Or(Box::new(a), Box::new(b)))
+                .unwrap()
+        })
+        .parse_next(input)
+}
+
+fn parse_and(input: &mut &str) -> PResult<Expr> {
+    separated(1.., parse_not, keyword("and"))
+        .map(|qs: Vec<_>| {
+            qs.into_iter()
+                .reduce(|a, b| Expr::And(Box::new(a), Box::new(b)))
+                .unwrap()
+        })
+        .parse_next(input)
+}
+
+fn parse_not(input: &mut &str) -> PResult<Expr> {
+    alt((
+        preceded(keyword("not"), parse_not).map(|q| Expr::Not(Box::new(q))),
+        delimited(ws('('), parse_query, ws(')')),
+        preceded((keyword("any"), keyword("round")), parse_not)
+            .map(|q| Expr::AnyRound(Box::new(q))),
+        preceded((keyword("every"), keyword("round")), parse_not)
+            .map(|q| Expr::EveryRound(Box::new(q))),
+        (parse_value, parse_comparison, parse_value).map(|(a, c, b)| Expr::Compare(a, c, b)),
+    ))
+    .parse_next(input)
+}
+
+fn parse_comparison(input: &mut &str) -> PResult<Comparison> {
+    ws(alt((
+        "<=".value(Comparison::LessOrEqual),
+        ">=".value(Comparison::GreaterOrEqual),
+        "!=".value(Comparison::NotEqual),
+        "<".value(Comparison::Less),
+        ">".value(Comparison::Greater),
+        "=".value(Comparison::Equal),
+    )))
+    .parse_next(input)
+}
+
+fn parse_colour(input: &mut &str) -> PResult<String> {
+    word()
+        .verify(|w: &str| !KEYWORDS.contains(&w))
+        .map(ToOwned::to_owned)
+        .parse_next(input)
+}
+
+fn parse_value(input: &mut &str) -> PResult<Value> {
+    alt((
+        ws(dec_uint).map(Value::Number),
+        (
+            alt((
+                keyword("max").value(Aggregate::Max),
+                keyword("min").value(Aggregate::Min),
+                keyword("sum").value(Aggregate::Sum),
+            )),
+            delimited(ws('('), parse_colour, ws(')')),
+        )
+            .map(|(aggregate, colour)| Value::Aggregate(aggregate, colour)),
+        keyword("id").value(Value::Id),
+        keyword("rounds").value(Value::Rounds),
+        parse_colour.map(Value::Count),
+    ))
+    .parse_next(input)
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let query = parse_query.parse(s).map_err(|e| {
+            QueryError::Invalid(format!("invalid query at column {}", e.offset() + 1))
+        })?;
+        query.check_scope(false)?;
+        Ok(Self(query))
+    }
+}
+
+impl Query {
+    #[must_use]
+    pub fn matches(&self, game: &Game) -> bool {
+        self.0.eval(game, None)
+    }
+}
+
+impl Expr {
+    fn check_scope(&self, in_round: bool) -> Result<(), QueryError> {
+        match self {
+            Self::And(a, b) | Self::Or(a, b) => {
+                a.check_scope(in_round)?;
+                b.check_scope(in_round)
+            }
+            Self::Not(q) => q.check_scope(in_round),
+            Self::AnyRound(q) | Self::EveryRound(q) => q.check_scope(true),
+            Self::Compare(a, _, b) => [a, b].into_iter().try_for_each(|v| match v {
+                Value::Count(colour) if !in_round => Err(QueryError::Invalid(format!(
+                    "'{colour}' is only counted inside a round, try max({colour})"
+                ))),
+                _ => Ok(()),
+            }),
+        }
+    }
+
+    fn eval(&self, game: &Game, round: Option<&Round>) -> bool {
+        match self {
+            Self::And(a, b) => a.eval(game, round) && b.eval(game, round),
+            Self::Or(a, b) => a.eval(game, round) || b.eval(game, round),
+            Self::Not(q) => !q.eval(game, round),
+            Self::AnyRound(q) => game.rounds.iter().any(|r| q.eval(game, Some(r))),
+            Self::EveryRound(q) => game.rounds.iter().all(|r| q.eval(game, Some(r))),
+            Self::Compare(a, comparison, b) => {
+                let (a, b) = (a.eval(game, round), b.eval(game, round));
+                match comparison {
+                    Comparison::Less => a < b,
+                    Comparison::LessOrEqual => a <= b,
+                    Comparison::Greater => a > b,
+                    Comparison::GreaterOrEqual => a >= b,
+                    Comparison::Equal => a == b,
+                    Comparison::NotEqual => a != b,
+                }
+            }
+        }
+    }
+}
+
+impl Value {
+    fn eval(&self, game: &Game, round: Option<&Round>) -> u32 {
+        let counts = |colour| game.rounds.iter().map(move |r| r.count(colour));
+        match self {
+            Self::Number(n) => *n,
+            Self::Count(colour) => round.expect("scope is checked").count(colour),
+            Self::Aggregate(Aggregate::Max, colour) => counts(colour).max().unwrap_or(0),
+            Self::Aggregate(Aggregate::Min, colour) => counts(colour).min().unwrap_or(0),
+            Self::Aggregate(Aggregate::Sum, colour) => counts(colour).sum(),
+            Self::Id => game.id,
+            Self::Rounds => u32::try_from(game.rounds.len()).unwrap(),
+        }
+    }
+}
+
+impl Games {
+    pub fn matching<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = &'a Game> + 'a {
+        self.games.iter().filter(|g| query.matches(g))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn ids(query: &str) -> Vec<u32> {
+        let games: Games = EXAMPLE.parse().unwrap();
+        let query: Query = query.parse().unwrap();
+        games.matching(&query).map(|g| g.id).collect()
+    }
+
+    #[test]
+    fn quantifiers() {
+        assert_eq!(ids("any round red > 10"), vec![3, 4]);
+        assert_eq!(ids("every round (blue <= 6 and green < 10)"), vec![1, 2, 5]);
+        assert_eq!(
+            ids("any round (red > 5) and every round blue <= 6"),
+            vec![3, 5]
+        );
+    }
+
+    #[test]
+    fn aggregates() {
+        assert_eq!(ids("max(red) >= 14"), vec![3, 4]);
+        assert_eq!(ids("sum(green) = 6 or rounds < 3"), vec![2, 5]);
+        assert_eq!(ids("not (id != 2)"), vec![2]);
+        assert_eq!(ids("min(red) = 0"), vec![1, 2]);
+    }
+
+    #[test]
+    fn colour_names() {
+        let games: Games = "Game 1: 3 dark_blue, 2 blue2\nGame 2: 1 blue2"
+            .parse()
+            .unwrap();
+        let query: Query = "any round dark_blue > 2 or max(blue2) < 2".parse().unwrap();
+        assert_eq!(
+            games.matching(&query).map(|g| g.id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(games.games[0].rounds[0].count("dark_blue"), 3);
+        // a keyword is only a keyword on its own
+        assert!("any round idx > 1".parse::<Query>().is_ok());
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            "id = 1 or id = 2 and id = 3".parse::<Query>().unwrap(),
+            "id = 1 or (id = 2 and id = 3)".parse().unwrap()
+        );
+        assert_eq!(ids("not id = 1 and id < 3"), vec![2]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            "red > 3".parse::<Query>(),
+            Err(QueryError::Invalid(
+                "'red' is only counted inside a round, try max(red)".to_owned()
+            ))
+        );
+        assert_eq!(
+            "max(red) >".parse::<Query>(),
+            Err(QueryError::Invalid("invalid query at column 11".to_owned()))
+        );
+    }
+}
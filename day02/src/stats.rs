@@ -0,0 +1,267 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{Cubes, Games};
+
+/// Summary statistics for a set of games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub games: usize,
+    /// How many games have each number of rounds.
+    pub rounds_per_game: BTreeMap<usize, usize>,
+    pub colours: BTreeMap<String, ColourStats>,
+    /// Every game's power, as part b counts it, smallest first.
+    pub powers: Vec<u32>,
+    /// The games closest to the limits of the bag, closest first.
+    pub nearest: Vec<Margin>,
+}
+
+/// The counts of one colour over the rounds that show it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColourStats {
+    /// How many rounds show each count.
+    pub counts: BTreeMap<u32, usize>,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+}
+
+/// How close a game comes to needing more cubes of a colour than a bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Margin {
+    pub id: u32,
+    pub colour: String,
+    /// Cubes of `colour` to spare, or negative if the game needs more than
+    /// the bag holds.
+    pub spare: i64,
+}
+
+impl Stats {
+    /// Gathers statistics for `games`, listing the `nearest` games closest to
+    /// the limits of `bag`.
+    #[must_use]
+    pub fn new(games: &Games, bag: &Cubes, nearest: usize) -> Self {
+        let mut rounds_per_game = BTreeMap::new();
+        let mut counts = BTreeMap::<_, BTreeMap<_, _>>::new();
+        for game in &games.games {
+            *rounds_per_game.entry(game.rounds.len()).or_default() += 1;
+            for (colour, &count) in game.rounds.iter().flat_map(|r| &r.cubes) {
+                *counts
+                    .entry(colour.clone())
+                    .or_default()
+                    .entry(count)
+                    .or_default() += 1;
+            }
+        }
+
+        let colours = counts
+            .into_iter()
+            .map(|(colour, counts)| {
+                let rounds = counts.values().sum::<usize>();
+                let total = counts
+                    .iter()
+                    .map(|(&count, &n)| u64::from(count) * n as u64)
+                    .sum::<u64>();
+                let stats = ColourStats {
+                    min: *counts.keys().next().unwrap(),
+                    max: *counts.keys().next_back().unwrap(),
+                    #[allow(clippy::cast_precision_loss)]
+                    mean: total as f64 / rounds as f64,
+                    counts,
+                };
+                (colour, stats)
+            })
+            .collect();
+
+        let mut powers = games.powers().collect::<Vec<_>>();
+        powers.sort_unstable();
+
+        let mut margins = games
+            .games
+            .iter()
+            .filter_map(|game| {
+                game.minimum_bag()
+                    .into_iter()
+                    .map(|(colour, count)| {
+                        let held = bag.get(&colour).copied().unwrap_or(0);
+                        let spare = i64::from(held) - i64::from(count);
+                        Margin {
+                            id: game.id,
+                            colour,
+                            spare,
+                        }
+                    })
+                    .min_by_key(|m| m.spare)
+            })
+            .collect::<Vec<_>>();
+        margins.sort_by_key(|m| (m.spare.abs(), m.id));
+        margins.truncate(nearest);
+
+        Self {
+            games: games.games.len(),
+            rounds_per_game,
+            colours,
+            powers,
+            nearest: margins,
+        }
+    }
+
+    /// The power at `fraction` of the way through the sorted powers.
+    fn power_quantile(&self, fraction: f64) -> u32 {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let i = ((self.powers.len() - 1) as f64 * fraction).round() as usize;
+        self.powers[i]
+    }
+
+    fn power_summary(&self) -> Vec<(&'static str, u64)> {
+        if self.powers.is_empty() {
+            return vec![];
+        }
+        vec![
+            ("min", u64::from(self.power_quantile(0.0))),
+            ("lower quartile", u64::from(self.power_quantile(0.25))),
+            ("median", u64::from(self.power_quantile(0.5))),
+            ("upper quartile", u64::from(self.power_quantile(0.75))),
+            ("max", u64::from(self.power_quantile(1.0))),
+            ("sum", self.powers.iter().copied().map(u64::from).sum()),
+        ]
+    }
+
+    /// The statistics laid out as aligned tables for reading.
+    #[must_use]
+    pub fn table(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "games: {}", self.games).unwrap();
+
+        writeln!(out, "\nrounds  games").unwrap();
+        for (rounds, games) in &self.rounds_per_game {
+            writeln!(out, "{rounds:>6}  {games:>5}").unwrap();
+        }
+
+        writeln!(out, "\ncolour      min    max     mean  rounds").unwrap();
+        for (colour, stats) in &self.colours {
+            writeln!(
+                out,
+                "{colour:<8} {:>6} {:>6} {:>8.2} {:>7}",
+                stats.min,
+                stats.max,
+                stats.mean,
+                stats.counts.values().sum::<usize>()
+            )
+            .unwrap();
+        }
+
+        for (colour, stats) in &self.colours {
+            writeln!(out, "\n{colour:>6}  rounds").unwrap();
+            for (count, rounds) in &stats.counts {
+                writeln!(out, "{count:>6}  {rounds:>6}").unwrap();
+            }
+        }
+
+        writeln!(out, "\npower").unwrap();
+        for (name, value) in self.power_summary() {
+            writeln!(out, "{name:<15} {value:>10}").unwrap();
+        }
+
+        writeln!(out, "\nnearest the limit").unwrap();
+        writeln!(out, "  game  colour    spare").unwrap();
+        for m in &self.nearest {
+            writeln!(out, "{:>6}  {:<8} {:>6}", m.id, m.colour, m.spare).unwrap();
+        }
+
+        out
+    }
+
+    /// The statistics as CSV, one `statistic,colour,subject,value` row per
+    /// figure, with the colour and subject left empty where they don't apply.
+    #[must_use]
+    pub fn csv(&self) -> String {
+        let mut out = "statistic,colour,subject,value\n".to_owned();
+
+        writeln!(out, "games,,,{}", self.games).unwrap();
+        for (rounds, games) in &self.rounds_per_game {
+            writeln!(out, "games with rounds,,{rounds},{games}").unwrap();
+        }
+        for (colour, stats) in &self.colours {
+            writeln!(out, "min,{colour},,{}", stats.min).unwrap();
+            writeln!(out, "max,{colour},,{}", stats.max).unwrap();
+            writeln!(out, "mean,{colour},,{}", stats.mean).unwrap();
+            for (count, rounds) in &stats.counts {
+                writeln!(out, "rounds with count,{colour},{count},{rounds}").unwrap();
+            }
+        }
+        for (name, value) in self.power_summary() {
+            writeln!(out, "power {name},,,{value}").unwrap();
+        }
+        for m in &self.nearest {
+            writeln!(out, "spare,{},{},{}", m.colour, m.id, m.spare).unwrap();
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standard_bag;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn stats() -> Stats {
+        Stats::new(&EXAMPLE.parse().unwrap(), &standard_bag(), 3)
+    }
+
+    #[test]
+    fn example_stats() {
+        let stats = stats();
+
+        assert_eq!(stats.games, 5);
+        assert_eq!(stats.rounds_per_game, BTreeMap::from([(2, 1), (3, 4)]));
+
+        let red = &stats.colours["red"];
+        assert_eq!((red.min, red.max), (1, 20));
+        assert!((red.mean - 61.0 / 11.0).abs() < 1e-9);
+        assert_eq!(red.counts[&1], 4);
+
+        assert_eq!(stats.powers, vec![12, 36, 48, 630, 1560]);
+        assert_eq!(
+            stats
+                .nearest
+                .iter()
+                .map(|m| (m.id, m.colour.as_str(), m.spare))
+                .collect::<Vec<_>>(),
+            vec![(4, "red", -2), (5, "red", 6), (1, "blue", 8)]
+        );
+    }
+
+    #[test]
+    fn csv_rows() {
+        let csv = stats().csv();
+
+        assert!(csv.starts_with("statistic,colour,subject,value\ngames,,,5\n"));
+        assert!(csv.contains("\nmax,red,,20\n"));
+        assert!(csv.contains("\nrounds with count,red,20,1\n"));
+        assert!(csv.contains("\npower sum,,,2286\n"));
+        assert!(csv.contains("\nspare,red,4,-2\n"));
+        for row in csv.lines() {
+            assert_eq!(row.split(',').count(), 4, "{row}");
+        }
+    }
+
+    #[test]
+    fn table_sections() {
+        let table = stats().table();
+
+        assert!(table.contains("red           1     20     5.55      11"));
+        assert!(table.contains("sum                   2286"));
+    }
+}
@@ -0,0 +1,115 @@
+use std::ops::Range;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day03::{generate, solve_a, solve_b};
+
+/// The numbers of a row as `(value, columns)`, and its symbols as
+/// `(symbol, column)`.
+type Row = (Vec<(u32, Range<usize>)>, Vec<(char, usize)>);
+
+fn tokens(input: &str) -> Vec<Row> {
+    input
+        .lines()
+        .map(|line| {
+            let (mut numbers, mut symbols) = (vec![], vec![]);
+            let mut start = None;
+            for (x, c) in line.char_indices().chain([(line.len(), '.')]) {
+                if c.is_ascii_digit() {
+                    start.get_or_insert(x);
+                    continue;
+                }
+                if let Some(s) = start.take() {
+                    numbers.push((line[s..x].parse().unwrap(), s..x));
+                }
+                if c != '.' {
+                    symbols.push((c, x));
+                }
+            }
+            (numbers, symbols)
+        })
+        .collect()
+}
+
+/// `solve_a` as it was before the grid: check every number against every
+/// symbol.
+fn pairwise_a(input: &str) -> u64 {
+    let rows = tokens(input);
+    let symbols = (0..)
+        .zip(&rows)
+        .flat_map(|(y, (_, s))| s.iter().map(move |&(_, x)| (x, y)))
+        .collect::<Vec<_>>();
+
+    (0..)
+        .zip(&rows)
+        .flat_map(|(y, (numbers, _))| numbers.iter().map(move |n| (y, n)))
+        .filter(|&(y, (_, columns)): &(usize, _)| {
+            let xs = columns.start.saturating_sub(1)..columns.end + 1;
+            let ys = y.saturating_sub(1)..y + 2;
+            symbols
+                .iter()
+                .any(|(x, y)| xs.contains(x) && ys.contains(y))
+        })
+        .map(|(_, (n, _))| u64::from(*n))
+        .sum()
+}
+
+/// `solve_b` as it was before the grid: check every `*` against every
+/// number.
+fn pairwise_b(input: &str) -> u64 {
+    let rows = tokens(input);
+    let numbers = (0..)
+        .zip(&rows)
+        .flat_map(|(y, (n, _))| n.iter().map(move |(n, columns)| (*n, columns, y)))
+        .collect::<Vec<(u32, _, usize)>>();
+
+    (0..)
+        .zip(&rows)
+        .flat_map(|(y, (_, s))| s.iter().map(move |&(c, x)| (c, x, y)))
+        .filter(|&(c, _, _)| c == '*')
+        .filter_map(|(_, x, y)| {
+            let adjacent = numbers
+                .iter()
+                .filter(|(_, columns, ny)| {
+                    (columns.start.saturating_sub(1)..columns.end + 1).contains(&x)
+                        && (ny.saturating_sub(1)..ny + 2).contains(&y)
+                })
+                .collect::<Vec<_>>();
+            match adjacent[..] {
+                [a, b] => Some(u64::from(a.0) * u64::from(b.0)),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+fn bench_solve(c: &mut Criterion) {
+    for (part, pairwise, grid) in [
+        (
+            "a",
+            pairwise_a as fn(&str) -> u64,
+            (|i| u64::from(solve_a(i))) as fn(&str) -> u64,
+        ),
+        ("b", pairwise_b, solve_b),
+    ] {
+        let mut group = c.benchmark_group(format!("day03 {part}"));
+
+        for size in [140, 500] {
+            let (input, a, b) = generate(0, size);
+            let expected = if part == "a" { a.map(u64::from) } else { b };
+            assert_eq!(Some(pairwise(&input)), expected);
+            assert_eq!(Some(grid(&input)), expected);
+
+            group.throughput(Throughput::Bytes(input.len() as u64));
+            group.bench_with_input(BenchmarkId::new("pairwise", size), &input, |b, i| {
+                b.iter(|| pairwise(i));
+            });
+            group.bench_with_input(BenchmarkId::new("grid", size), &input, |b, i| {
+                b.iter(|| grid(i));
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_solve);
+criterion_main!(benches);
@@ -0,0 +1,144 @@
+use std::ops::Range;
+
+use crate::{Cell, GearRule, Schematic};
+
+/// A schematic being edited a cell at a time, keeping its part number sum
+/// and gear ratio sum up to date. Each edit only looks at the numbers and
+/// symbols around the cell.
+#[derive(Debug, Clone)]
+pub struct Editor {
+    schematic: Schematic,
+    rule: GearRule,
+    part_sum: u64,
+    ratio_sum: u64,
+}
+
+impl Editor {
+    #[must_use]
+    pub fn new(schematic: Schematic, rule: GearRule) -> Self {
+        let part_sum = (0..schematic.numbers().len())
+            .filter(|&id| schematic.is_part(id))
+            .map(|id| u64::from(schematic.numbers()[id].value))
+            .sum();
+        let ratio_sum = schematic.gears(&rule).map(|g| g.ratio).sum();
+        Self {
+            schematic,
+            rule,
+            part_sum,
+            ratio_sum,
+        }
+    }
+
+    #[must_use]
+    pub fn schematic(&self) -> &Schematic {
+        &self.schematic
+    }
+
+    /// The sum of the part numbers, as part a counts it.
+    #[must_use]
+    pub fn part_sum(&self) -> u64 {
+        self.part_sum
+    }
+
+    /// The sum of the ratios of the gears under the editor's rule.
+    #[must_use]
+    pub fn ratio_sum(&self) -> u64 {
+        self.ratio_sum
+    }
+
+    /// Writes `c` to a cell, as [`Schematic::set`] does, and updates the
+    /// sums.
+    pub fn set(&mut self, row: usize, column: usize, c: char) {
+        // only numbers and symbols next to the span can change, so take
+        // away what they add to the sums and put it back after the edit
+        let span = self.schematic.edit_span(row, column);
+        let (parts, ratios) = self.sums_around(row, span.clone());
+        self.part_sum -= parts;
+        self.ratio_sum -= ratios;
+
+        self.schematic.set(row, column, c);
+
+        let (parts, ratios) = self.sums_around(row, span);
+        self.part_sum += parts;
+        self.ratio_sum += ratios;
+    }
+
+    /// What the numbers and symbols with a cell next to `columns` of `row`
+    /// add to the sums.
+    fn sums_around(&self, row: usize, columns: Range<usize>) -> (u64, u64) {
+        let (mut numbers, mut symbols) = (vec![], vec![]);
+        for y in row.saturating_sub(1)..=row + 1 {
+            for x in columns.start.saturating_sub(1)..=columns.end {
+                match self.schematic.cell(y, x) {
+                    Cell::Number(id) if !numbers.contains(&id) => numbers.push(id),
+                    Cell::Symbol(id) => symbols.push(id),
+                    _ => {}
+                }
+            }
+        }
+
+        let parts = numbers
+            .into_iter()
+            .filter(|&id| self.schematic.is_part(id))
+            .map(|id| u64::from(self.schematic.numbers()[id].value))
+            .sum();
+        let ratios = symbols
+            .into_iter()
+            .filter_map(|id| self.schematic.gear(id, &self.rule))
+            .map(|g| g.ratio)
+            .sum();
+        (parts, ratios)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{generate, solve_a, solve_b};
+
+    #[test]
+    fn joins_and_splits() {
+        let mut editor = Editor::new(Schematic::parse("12.34\n.....\n#...."), GearRule::part_b());
+        assert_eq!((editor.part_sum(), editor.ratio_sum()), (0, 0));
+
+        editor.set(0, 2, '5');
+        assert_eq!(editor.schematic().numbers()[0].value, 12_534);
+        assert_eq!(editor.schematic().to_string(), "12534\n.....\n#....");
+
+        editor.set(1, 0, '*');
+        assert_eq!((editor.part_sum(), editor.ratio_sum()), (12_534, 0));
+
+        editor.set(0, 1, '.');
+        assert_eq!(editor.schematic().to_string(), "1.534\n*....\n#....");
+        assert_eq!((editor.part_sum(), editor.ratio_sum()), (1, 0));
+
+        editor.set(1, 1, '7');
+        assert_eq!((editor.part_sum(), editor.ratio_sum()), (1 + 7, 7));
+    }
+
+    #[test]
+    fn matches_solving_again() {
+        const SIZE: usize = 30;
+        const CELLS: &[u8] = b"0123456789.....*#";
+
+        let (input, _, _) = generate(2, SIZE);
+        let mut editor = Editor::new(Schematic::parse(&input), GearRule::part_b());
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..500 {
+            let (row, column) = (rng.gen_range(0..SIZE), rng.gen_range(0..SIZE));
+            let mut c = char::from(CELLS[rng.gen_range(0..CELLS.len())]);
+            // keep numbers small enough for a u32
+            if c.is_ascii_digit() && editor.schematic().edit_span(row, column).len() > 8 {
+                c = '.';
+            }
+            editor.set(row, column, c);
+
+            let text = editor.schematic().to_string();
+            assert_eq!(editor.part_sum(), u64::from(solve_a(&text)), "{text}");
+            assert_eq!(editor.ratio_sum(), solve_b(&text), "{text}");
+        }
+    }
+}
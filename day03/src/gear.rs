@@ -0,0 +1,154 @@
+use crate::Schematic;
+
+/// What makes a symbol a gear, and how its ratio is worked out from the
+/// numbers touching it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    /// The symbols that can be gears.
+    pub symbols: Vec<char>,
+    /// How many numbers a gear must touch.
+    pub count: Count,
+    pub combine: Combine,
+}
+
+/// A constraint on the number of numbers touching a gear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    Exactly(usize),
+    AtLeast(usize),
+    /// Between the two counts, inclusive.
+    Between(usize, usize),
+}
+
+/// How the numbers touching a gear are combined into its ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combine {
+    Product,
+    Sum,
+    Max,
+}
+
+/// A symbol that is a gear under some [`GearRule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gear {
+    /// The id of the gear's symbol.
+    pub symbol: usize,
+    /// The ids of the numbers touching it.
+    pub numbers: Vec<usize>,
+    pub ratio: u64,
+}
+
+impl GearRule {
+    /// The puzzle's rule: a `*` touching exactly two numbers, whose ratio is
+    /// their product.
+    #[must_use]
+    pub fn part_b() -> Self {
+        Self {
+            symbols: vec!['*'],
+            count: Count::Exactly(2),
+            combine: Combine::Product,
+        }
+    }
+}
+
+impl Count {
+    #[must_use]
+    pub fn allows(self, n: usize) -> bool {
+        match self {
+            Self::Exactly(count) => n == count,
+            Self::AtLeast(count) => n >= count,
+            Self::Between(least, most) => (least..=most).contains(&n),
+        }
+    }
+}
+
+impl Combine {
+    /// Combines `values`, giving 0 for none. Products and sums too big for
+    /// a `u64` stop at `u64::MAX`.
+    pub fn apply(self, values: impl IntoIterator<Item = u32>) -> u64 {
+        let mut values = values.into_iter().map(u64::from).peekable();
+        if values.peek().is_none() {
+            return 0;
+        }
+        match self {
+            Self::Product => values.fold(1, u64::saturating_mul),
+            Self::Sum => values.fold(0, u64::saturating_add),
+            Self::Max => values.max().unwrap(),
+        }
+    }
+}
+
+impl Schematic {
+    /// The gear at symbol `id` under `rule`, if it is one.
+    #[must_use]
+    pub fn gear(&self, id: usize, rule: &GearRule) -> Option<Gear> {
+        if !rule.symbols.contains(&self.symbols()[id].symbol) {
+            return None;
+        }
+        let numbers = self.adjacent_numbers(id);
+        rule.count.allows(numbers.len()).then(|| Gear {
+            symbol: id,
+            ratio: rule
+                .combine
+                .apply(numbers.iter().map(|&n| self.numbers()[n].value)),
+            numbers,
+        })
+    }
+
+    /// The symbols that are gears under `rule`, in the order they appear.
+    pub fn gears<'a>(&'a self, rule: &'a GearRule) -> impl Iterator<Item = Gear> + 'a {
+        (0..self.symbols().len()).filter_map(|id| self.gear(id, rule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    fn ratios(rule: &GearRule) -> Vec<u64> {
+        Schematic::parse(INPUT)
+            .gears(rule)
+            .map(|g| g.ratio)
+            .collect()
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(Combine::Product.apply([u32::MAX; 3]), u64::MAX);
+        assert_eq!(Combine::Product.apply([2, 3, 7]), 42);
+        assert_eq!(Combine::Sum.apply([]), 0);
+    }
+
+    #[test]
+    fn part_b_preset() {
+        assert_eq!(ratios(&GearRule::part_b()), vec![16_345, 451_490]);
+    }
+
+    #[test]
+    fn variant_rules() {
+        let any_symbol = GearRule {
+            symbols: "*#+$".chars().collect(),
+            count: Count::AtLeast(1),
+            combine: Combine::Sum,
+        };
+        assert_eq!(ratios(&any_symbol), vec![502, 633, 617, 592, 664, 1353]);
+
+        let lonely = GearRule {
+            count: Count::Between(0, 1),
+            combine: Combine::Max,
+            ..GearRule::part_b()
+        };
+        assert_eq!(ratios(&lonely), vec![617]);
+    }
+}
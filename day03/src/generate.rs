@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&";
+
+/// Generates a `size` by `size` schematic, with the answers to both parts.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<u64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut lines = vec![];
+    // (value, id) of the number covering each cell
+    let mut numbers: HashMap<(usize, usize), (u32, usize)> = HashMap::new();
+    let mut symbols = vec![];
+
+    for y in 0..size {
+        let mut line = String::new();
+        while line.len() < size {
+            let x = line.len();
+            let roll = rng.gen_range(0..10);
+            if roll < 2 && !line.ends_with(|c: char| c.is_ascii_digit()) {
+                let n: u32 = rng.gen_range(1..1000);
+                let digits = n.to_string();
+                if x + digits.len() > size {
+                    continue;
+                }
+                let id = numbers.len();
+                for dx in 0..digits.len() {
+                    numbers.insert((x + dx, y), (n, id));
+                }
+                line.push_str(&digits);
+            } else if roll < 3 {
+                let c = char::from(*SYMBOLS.choose(&mut rng).unwrap());
+                symbols.push((x, y, c));
+                line.push(c);
+            } else {
+                line.push('.');
+            }
+        }
+        lines.push(line);
+    }
+
+    let adjacent = |x: usize, y: usize| {
+        let mut found: Vec<(u32, usize)> = vec![];
+        for ny in y.saturating_sub(1)..=y + 1 {
+            for nx in x.saturating_sub(1)..=x + 1 {
+                if let Some(&n) = numbers.get(&(nx, ny)) {
+                    if !found.contains(&n) {
+                        found.push(n);
+                    }
+                }
+            }
+        }
+        found
+    };
+
+    let mut parts = vec![];
+    let mut sum_b = 0;
+    for &(x, y, c) in &symbols {
+        let found = adjacent(x, y);
+        if let ('*', [(a, _), (b, _)]) = (c, &found[..]) {
+            sum_b += u64::from(a * b);
+        }
+        parts.extend(found);
+    }
+    parts.sort_unstable_by_key(|&(_, id)| id);
+    parts.dedup();
+    let sum_a = parts.iter().map(|&(n, _)| n).sum();
+
+    (lines.join("\n"), Some(sum_a), Some(sum_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 60);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+}
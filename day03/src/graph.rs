@@ -0,0 +1,250 @@
+use std::fmt::Write;
+
+use crate::Schematic;
+
+/// A number or a symbol of a schematic, by id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Node {
+    Number(usize),
+    Symbol(usize),
+}
+
+/// Numbers and symbols joined to each other, directly or through other
+/// numbers and symbols. Ids are in the order they appear.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Component {
+    pub numbers: Vec<usize>,
+    pub symbols: Vec<usize>,
+}
+
+/// A schematic as a bipartite graph, with an edge between each number and
+/// each symbol touching it.
+#[derive(Debug, Clone)]
+pub struct Graph<'a> {
+    schematic: &'a Schematic,
+    /// The symbols touching each number.
+    number_edges: Vec<Vec<usize>>,
+    /// The numbers touching each symbol.
+    symbol_edges: Vec<Vec<usize>>,
+    components: Vec<Component>,
+    number_components: Vec<usize>,
+    symbol_components: Vec<usize>,
+}
+
+impl Schematic {
+    #[must_use]
+    pub fn graph(&self) -> Graph<'_> {
+        Graph::new(self)
+    }
+}
+
+impl<'a> Graph<'a> {
+    #[must_use]
+    pub fn new(schematic: &'a Schematic) -> Self {
+        let number_edges = (0..schematic.numbers().len())
+            .map(|id| schematic.adjacent_symbols(id))
+            .collect::<Vec<_>>();
+        let symbol_edges = (0..schematic.symbols().len())
+            .map(|id| schematic.adjacent_numbers(id))
+            .collect::<Vec<_>>();
+
+        let mut components = vec![];
+        let mut number_components = vec![usize::MAX; number_edges.len()];
+        let mut symbol_components = vec![usize::MAX; symbol_edges.len()];
+
+        let starts = (0..number_edges.len())
+            .map(Node::Number)
+            .chain((0..symbol_edges.len()).map(Node::Symbol));
+        for start in starts {
+            let seen = match start {
+                Node::Number(id) => number_components[id],
+                Node::Symbol(id) => symbol_components[id],
+            };
+            if seen != usize::MAX {
+                continue;
+            }
+
+            let index = components.len();
+            let mut component = Component::default();
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                match node {
+                    Node::Number(id) if number_components[id] == usize::MAX => {
+                        number_components[id] = index;
+                        component.numbers.push(id);
+                        stack.extend(number_edges[id].iter().copied().map(Node::Symbol));
+                    }
+                    Node::Symbol(id) if symbol_components[id] == usize::MAX => {
+                        symbol_components[id] = index;
+                        component.symbols.push(id);
+                        stack.extend(symbol_edges[id].iter().copied().map(Node::Number));
+                    }
+                    _ => {}
+                }
+            }
+            component.numbers.sort_unstable();
+            component.symbols.sort_unstable();
+            components.push(component);
+        }
+
+        Self {
+            schematic,
+            number_edges,
+            symbol_edges,
+            components,
+            number_components,
+            symbol_components,
+        }
+    }
+
+    /// Every edge, as `(number, symbol)` ids.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..)
+            .zip(&self.number_edges)
+            .flat_map(|(number, symbols)| symbols.iter().map(move |&symbol| (number, symbol)))
+    }
+
+    /// The nodes joined to `node` by an edge.
+    #[must_use]
+    pub fn neighbours(&self, node: Node) -> Vec<Node> {
+        match node {
+            Node::Number(id) => self.number_edges[id]
+                .iter()
+                .copied()
+                .map(Node::Symbol)
+                .collect(),
+            Node::Symbol(id) => self.symbol_edges[id]
+                .iter()
+                .copied()
+                .map(Node::Number)
+                .collect(),
+        }
+    }
+
+    /// Every connected component, ordered by its first number or, for a lone
+    /// symbol, by the symbol.
+    #[must_use]
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+
+    /// The component holding `node`, such as all the parts attached to a
+    /// cluster of symbols.
+    #[must_use]
+    pub fn component(&self, node: Node) -> &Component {
+        let index = match node {
+            Node::Number(id) => self.number_components[id],
+            Node::Symbol(id) => self.symbol_components[id],
+        };
+        &self.components[index]
+    }
+
+    /// The numbers touching no symbol.
+    pub fn orphan_numbers(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.number_edges.len()).filter(|&id| self.number_edges[id].is_empty())
+    }
+
+    /// The symbols touching no number.
+    pub fn orphan_symbols(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.symbol_edges.len()).filter(|&id| self.symbol_edges[id].is_empty())
+    }
+
+    /// The graph in Graphviz's DOT language, with numbers as ellipses and
+    /// symbols as boxes, each component in its own cluster.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut out = "graph schematic {\n".to_owned();
+
+        for (i, component) in self.components.iter().enumerate() {
+            writeln!(out, "  subgraph cluster_{i} {{").unwrap();
+            for &id in &component.numbers {
+                let number = &self.schematic.numbers()[id];
+                writeln!(out, "    n{id} [label=\"{}\"];", number.value).unwrap();
+            }
+            for &id in &component.symbols {
+                let symbol = self.schematic.symbols()[id].symbol;
+                let label = match symbol {
+                    '"' | '\\' => format!("\\{symbol}"),
+                    _ => symbol.to_string(),
+                };
+                writeln!(out, "    s{id} [label=\"{label}\", shape=box];").unwrap();
+            }
+            out.push_str("  }\n");
+        }
+        for (number, symbol) in self.edges() {
+            writeln!(out, "  n{number} -- s{symbol};").unwrap();
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn components() {
+        let schematic = Schematic::parse(INPUT);
+        let graph = schematic.graph();
+
+        assert_eq!(graph.edges().count(), 8);
+        assert_eq!(graph.components().len(), 8);
+        assert_eq!(
+            graph.component(Node::Symbol(5)),
+            &Component {
+                numbers: vec![7, 9],
+                symbols: vec![5]
+            }
+        );
+        assert_eq!(graph.neighbours(Node::Number(0)), vec![Node::Symbol(0)]);
+    }
+
+    #[test]
+    fn orphans() {
+        let schematic = Schematic::parse("1.2\n.*.\n...\n#.3");
+        let graph = schematic.graph();
+
+        assert_eq!(graph.orphan_numbers().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(graph.orphan_symbols().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            graph.component(Node::Number(1)),
+            &Component {
+                numbers: vec![0, 1],
+                symbols: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn dot() {
+        let schematic = Schematic::parse("12\"\n..3");
+        let dot = schematic.graph().to_dot();
+
+        assert_eq!(
+            dot,
+            "graph schematic {
+  subgraph cluster_0 {
+    n0 [label=\"12\"];
+    n1 [label=\"3\"];
+    s0 [label=\"\\\"\", shape=box];
+  }
+  n0 -- s0;
+  n1 -- s0;
+}
+"
+        );
+    }
+}
@@ -4,18 +4,30 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod edit;
+mod gear;
+mod generate;
+mod graph;
+mod render;
+mod schematic;
+
+pub use edit::Editor;
+pub use gear::{Combine, Count, Gear, GearRule};
+pub use generate::generate;
+pub use graph::{Component, Graph, Node};
+pub use schematic::{Cell, Number, Schematic, Symbol};
+
 use std::ops::Range;
 
-use itertools::Itertools;
 use winnow::{
     ascii::dec_uint,
-    combinator::{alt, delimited, repeat},
+    combinator::{alt, preceded, repeat, terminated},
     token::any,
     Located, PResult, Parser,
 };
 
 #[derive(Debug)]
-enum Token {
+pub(crate) enum Token {
     Number(u32),
     Symbol(char),
 }
@@ -24,16 +36,22 @@ fn parse_dots(input: &mut Located<&str>) -> PResult<()> {
     repeat(0.., '.').parse_next(input)
 }
 
-fn parse_line(input: &mut Located<&str>) -> PResult<Vec<(Token, Range<usize>)>> {
-    repeat(
-        0..,
-        delimited(
-            parse_dots,
-            alt((
-                dec_uint.map(Token::Number).with_span(),
-                any.map(Token::Symbol).with_span(),
-            )),
-            parse_dots,
+/// The numbers and symbols in a row, with their byte spans.
+///
+/// The leading dots are read once and then each token takes the dots after
+/// it, so a row of only dots, or one ending in dots, is read to the end.
+pub(crate) fn parse_line(input: &mut Located<&str>) -> PResult<Vec<(Token, Range<usize>)>> {
+    preceded(
+        parse_dots,
+        repeat(
+            0..,
+            terminated(
+                alt((
+                    dec_uint.map(Token::Number).with_span(),
+                    any.map(Token::Symbol).with_span(),
+                )),
+                parse_dots,
+            ),
         ),
     )
     .parse_next(input)
@@ -41,98 +59,22 @@ fn parse_line(input: &mut Located<&str>) -> PResult<Vec<(Token, Range<usize>)>>
 
 #[must_use]
 pub fn solve_a(input: &str) -> u32 {
-    let rows = input
-        .lines()
-        .map(|line| parse_line.parse(Located::new(line)).unwrap())
-        .enumerate()
-        .collect_vec();
-
-    let symbol_coordinates = rows
-        .iter()
-        .flat_map(|(y, row)| {
-            row.iter().filter_map(|(t, r)| {
-                if let Token::Symbol(_) = t {
-                    Some((r.start, *y))
-                } else {
-                    None
-                }
-            })
-        })
-        .collect_vec();
-
-    rows.iter()
-        .flat_map(|(y, row)| {
-            row.iter().filter_map(|(t, r)| {
-                if let Token::Number(n) = t {
-                    let x_range = Range {
-                        start: r.start.saturating_sub(1),
-                        end: r.end.saturating_add(1),
-                    };
-
-                    let y_range = Range {
-                        start: y.saturating_sub(1),
-                        end: y.saturating_add(2), //range is exclusive above
-                    };
-
-                    symbol_coordinates
-                        .iter()
-                        .any(|(symbol_x, symbol_y)| {
-                            x_range.contains(symbol_x) && y_range.contains(symbol_y)
-                        })
-                        .then_some(n)
-                } else {
-                    None
-                }
-            })
-        })
+    let schematic = Schematic::parse(input);
+    (0..schematic.numbers().len())
+        .filter(|&id| schematic.is_part(id))
+        .map(|id| schematic.numbers()[id].value)
         .sum()
 }
 
 #[must_use]
-pub fn solve_b(input: &str) -> u32 {
-    let rows = input
-        .lines()
-        .map(|line| parse_line.parse(Located::new(line)).unwrap())
-        .enumerate()
-        .collect_vec();
-
-    let number_areas = rows
-        .iter()
-        .flat_map(|(y, row)| {
-            row.iter().filter_map(|(t, r)| {
-                if let Token::Number(n) = t {
-                    Some((
-                        *n,
-                        Range {
-                            start: r.start.saturating_sub(1),
-                            end: r.end.saturating_add(1),
-                        },
-                        Range {
-                            start: y.saturating_sub(1),
-                            end: y.saturating_add(2), //range is exclusive above
-                        },
-                    ))
-                } else {
-                    None
-                }
-            })
-        })
-        .collect_vec();
-
-    rows.iter()
-        .flat_map(|(y, row)| {
-            row.iter().filter_map(|(t, r)| match t {
-                Token::Symbol('*') => number_areas
-                    .iter()
-                    .filter_map(|(n, num_x, num_y)| {
-                        (num_x.contains(&r.start) && num_y.contains(y)).then_some(n)
-                    })
-                    .collect_tuple()
-                    .map(|(a, b)| a * b),
-                _ => None,
-            })
-        })
-        .sum()
+pub fn solve_b(input: &str) -> u64 {
+    solve_b_with(input, &GearRule::part_b())
+}
+
+/// The sum of the ratios of the gears under `rule`.
+#[must_use]
+pub fn solve_b_with(input: &str, rule: &GearRule) -> u64 {
+    Schematic::parse(input).gears(rule).map(|g| g.ratio).sum()
 }
 
 #[cfg(test)]
@@ -150,6 +92,35 @@ mod tests {
 ...$.*....
 .664.598..";
 
+    fn tokens(row: &str) -> Vec<(String, Range<usize>)> {
+        parse_line
+            .parse(Located::new(row))
+            .unwrap()
+            .into_iter()
+            .map(|(token, span)| (format!("{token:?}"), span))
+            .collect()
+    }
+
+    #[test]
+    fn dot_rows() {
+        assert_eq!(tokens(""), vec![]);
+        assert_eq!(tokens("....."), vec![]);
+        assert_eq!(
+            tokens("..12..#.."),
+            vec![
+                ("Number(12)".to_owned(), 2..4),
+                ("Symbol('#')".to_owned(), 6..7)
+            ]
+        );
+        assert_eq!(
+            tokens("7*"),
+            vec![
+                ("Number(7)".to_owned(), 0..1),
+                ("Symbol('*')".to_owned(), 1..2)
+            ]
+        );
+    }
+
     #[test]
     fn example_a() {
         assert_eq!(solve_a(INPUT), 4361);
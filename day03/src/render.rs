@@ -0,0 +1,198 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{Cell, GearRule, Schematic};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Empty,
+    Part,
+    NotPart,
+    /// A gear, with its ratio.
+    Gear(u64),
+    Symbol,
+}
+
+/// A row split into runs of cells with the same style, with the ratios of
+/// the row's gears.
+struct Row {
+    runs: Vec<(Style, String)>,
+    ratios: Vec<(char, u64)>,
+}
+
+fn annotate(schematic: &Schematic, rule: &GearRule) -> Vec<Row> {
+    let parts = (0..schematic.numbers().len())
+        .map(|id| schematic.is_part(id))
+        .collect::<Vec<_>>();
+    let gears = schematic
+        .gears(rule)
+        .map(|g| (g.symbol, g.ratio))
+        .collect::<HashMap<_, _>>();
+
+    (0..schematic.rows())
+        .map(|row| {
+            let mut runs: Vec<(Style, String)> = vec![];
+            let mut ratios = vec![];
+            for column in 0..schematic.width(row) {
+                let (style, c) = match schematic.cell(row, column) {
+                    Cell::Empty => (Style::Empty, '.'),
+                    Cell::Number(id) => {
+                        let style = if parts[id] {
+                            Style::Part
+                        } else {
+                            Style::NotPart
+                        };
+                        (style, schematic.char_at(row, column))
+                    }
+                    Cell::Symbol(id) => {
+                        let c = schematic.symbols()[id].symbol;
+                        match gears.get(&id) {
+                            Some(&ratio) => {
+                                ratios.push((c, ratio));
+                                (Style::Gear(ratio), c)
+                            }
+                            None => (Style::Symbol, c),
+                        }
+                    }
+                };
+                match runs.last_mut() {
+                    Some((last, text)) if *last == style && !matches!(style, Style::Gear(_)) => {
+                        text.push(c);
+                    }
+                    _ => runs.push((style, c.to_string())),
+                }
+            }
+            Row { runs, ratios }
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Schematic {
+    /// The schematic with ANSI colours: part numbers green, other numbers
+    /// red, gears under `rule` highlighted with their ratios at the end of
+    /// the row, and other symbols dimmed.
+    #[must_use]
+    pub fn to_ansi(&self, rule: &GearRule) -> String {
+        let mut out = String::new();
+        for row in annotate(self, rule) {
+            for (style, text) in row.runs {
+                let code = match style {
+                    Style::Empty => {
+                        out.push_str(&text);
+                        continue;
+                    }
+                    Style::Part => "32",
+                    Style::NotPart => "31",
+                    Style::Gear(_) => "1;30;43",
+                    Style::Symbol => "2",
+                };
+                write!(out, "\x1b[{code}m{text}\x1b[0m").unwrap();
+            }
+            for (c, ratio) in row.ratios {
+                write!(out, "  \x1b[1;33m{c} {ratio}\x1b[0m").unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The same annotation as [`Schematic::to_ansi`], as an HTML page. Each
+    /// gear's ratio is also its tooltip.
+    #[must_use]
+    pub fn to_html(&self, rule: &GearRule) -> String {
+        let mut out = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Schematic</title>
+<style>
+.part { color: green; }
+.not-part { color: red; }
+.gear { background: gold; font-weight: bold; }
+.symbol { opacity: 0.5; }
+.ratio { color: darkgoldenrod; }
+</style>
+</head>
+<body>
+<pre>
+"
+        .to_owned();
+
+        for row in annotate(self, rule) {
+            for (style, text) in row.runs {
+                let text = escape(&text);
+                match style {
+                    Style::Empty => out.push_str(&text),
+                    Style::Part => write!(out, "<span class=\"part\">{text}</span>").unwrap(),
+                    Style::NotPart => {
+                        write!(out, "<span class=\"not-part\">{text}</span>").unwrap();
+                    }
+                    Style::Gear(ratio) => write!(
+                        out,
+                        "<span class=\"gear\" title=\"ratio {ratio}\">{text}</span>"
+                    )
+                    .unwrap(),
+                    Style::Symbol => write!(out, "<span class=\"symbol\">{text}</span>").unwrap(),
+                }
+            }
+            for (c, ratio) in row.ratios {
+                let c = escape(&c.to_string());
+                write!(out, "  <span class=\"ratio\">{c} {ratio}</span>").unwrap();
+            }
+            out.push('\n');
+        }
+
+        out.push_str("</pre>\n</body>\n</html>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn ansi() {
+        let ansi = Schematic::parse(INPUT).to_ansi(&GearRule::part_b());
+        let lines = ansi.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m..");
+        assert_eq!(
+            lines[1],
+            "...\x1b[1;30;43m*\x1b[0m......  \x1b[1;33m* 16345\x1b[0m"
+        );
+        assert_eq!(lines[4], "\x1b[32m617\x1b[0m\x1b[2m*\x1b[0m......");
+    }
+
+    #[test]
+    fn html() {
+        let html = Schematic::parse("<1.07\n..&.").to_html(&GearRule {
+            symbols: vec!['<', '&'],
+            ..GearRule::part_b()
+        });
+
+        assert!(html.contains(
+            "\n<span class=\"symbol\">&lt;</span><span class=\"part\">1</span>\
+             .<span class=\"part\">07</span>\n\
+             ..<span class=\"gear\" title=\"ratio 7\">&amp;</span>.  \
+             <span class=\"ratio\">&amp; 7</span>\n</pre>"
+        ));
+    }
+}
@@ -0,0 +1,342 @@
+use std::{fmt::Display, ops::Range};
+
+use winnow::{Located, Parser};
+
+use crate::{parse_line, Token};
+
+/// A number written across one or more cells of a row. Columns count
+/// characters, not bytes, so a multi-byte symbol takes up one cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+/// A symbol in a single cell. Any character other than a digit or `.` is a
+/// symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub row: usize,
+    pub column: usize,
+}
+
+/// What a cell of a schematic holds, by id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Number(usize),
+    Symbol(usize),
+}
+
+/// An engine schematic, with a grid mapping each cell to the number or
+/// symbol in it so that looking up a neighbour takes constant time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    cells: Vec<Vec<Cell>>,
+}
+
+impl Schematic {
+    #[must_use]
+    pub fn parse(input: &str) -> Self {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut cells = vec![];
+
+        for (row, line) in input.lines().enumerate() {
+            // the spans are byte offsets, so map them to character columns
+            let mut column = vec![0; line.len() + 1];
+            for (i, (byte, _)) in line.char_indices().enumerate() {
+                column[byte] = i;
+            }
+            column[line.len()] = line.chars().count();
+
+            let mut cell_row = vec![Cell::Empty; column[line.len()]];
+            for (token, span) in parse_line.parse(Located::new(line)).unwrap() {
+                let columns = column[span.start]..column[span.end];
+                match token {
+                    Token::Number(value) => {
+                        cell_row[columns.clone()].fill(Cell::Number(numbers.len()));
+                        numbers.push(Number {
+                            value,
+                            row,
+                            columns,
+                        });
+                    }
+                    Token::Symbol(symbol) => {
+                        cell_row[columns.start] = Cell::Symbol(symbols.len());
+                        symbols.push(Symbol {
+                            symbol,
+                            row,
+                            column: columns.start,
+                        });
+                    }
+                }
+            }
+            cells.push(cell_row);
+        }
+
+        Self {
+            numbers,
+            symbols,
+            cells,
+        }
+    }
+
+    /// Every number, indexed by the ids the other methods use.
+    #[must_use]
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// Every symbol, indexed by the ids the other methods use.
+    #[must_use]
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// The number of rows.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cells in `row`.
+    #[must_use]
+    pub fn width(&self, row: usize) -> usize {
+        self.cells[row].len()
+    }
+
+    /// The cell at `row` and `column`, or empty if it is off the schematic.
+    #[must_use]
+    pub fn cell(&self, row: usize, column: usize) -> Cell {
+        self.cells
+            .get(row)
+            .and_then(|r| r.get(column))
+            .copied()
+            .unwrap_or(Cell::Empty)
+    }
+
+    /// The character written at `row` and `column`, or `.` if it is off the
+    /// schematic.
+    #[must_use]
+    pub fn char_at(&self, row: usize, column: usize) -> char {
+        match self.cell(row, column) {
+            Cell::Empty => '.',
+            Cell::Number(id) => {
+                let number = &self.numbers[id];
+                let digits = format!("{:0width$}", number.value, width = number.columns.len());
+                char::from(digits.as_bytes()[column - number.columns.start])
+            }
+            Cell::Symbol(id) => self.symbols[id].symbol,
+        }
+    }
+
+    /// The columns of `row` that writing to `column` can change: the cell
+    /// and any numbers either side of it, which it could join or split.
+    #[must_use]
+    pub fn edit_span(&self, row: usize, column: usize) -> Range<usize> {
+        let mut span = column..column + 1;
+        for x in [column.checked_sub(1), Some(column), Some(column + 1)] {
+            if let Some(Cell::Number(id)) = x.map(|x| self.cell(row, x)) {
+                let columns = &self.numbers[id].columns;
+                span = span.start.min(columns.start)..span.end.max(columns.end);
+            }
+        }
+        span
+    }
+
+    /// Writes `c` to the cell at `row` and `column`, which must be on the
+    /// schematic, and reads the [`edit_span`](Schematic::edit_span) around
+    /// it again, so digits can join into one number or split into two.
+    /// Returns the span.
+    ///
+    /// Ids of numbers and symbols outside the span can change, as removed
+    /// ids are filled from the end. Panics if the edit joins digits into a
+    /// number too big for a `u32`.
+    pub fn set(&mut self, row: usize, column: usize, c: char) -> Range<usize> {
+        assert!(column < self.width(row), "no cell at {row}, {column}");
+        let span = self.edit_span(row, column);
+        let mut text = span
+            .clone()
+            .map(|x| self.char_at(row, x))
+            .collect::<Vec<_>>();
+        text[column - span.start] = c;
+
+        let (mut numbers, mut symbols) = (vec![], vec![]);
+        for x in span.clone() {
+            match self.cells[row][x] {
+                Cell::Number(id) if !numbers.contains(&id) => numbers.push(id),
+                Cell::Symbol(id) => symbols.push(id),
+                _ => {}
+            }
+            self.cells[row][x] = Cell::Empty;
+        }
+        // highest first, so no id is moved before it is removed
+        numbers.sort_unstable_by(|a, b| b.cmp(a));
+        symbols.sort_unstable_by(|a, b| b.cmp(a));
+        for id in numbers {
+            self.numbers.swap_remove(id);
+            if let Some(moved) = self.numbers.get(id) {
+                self.cells[moved.row][moved.columns.clone()].fill(Cell::Number(id));
+            }
+        }
+        for id in symbols {
+            self.symbols.swap_remove(id);
+            if let Some(moved) = self.symbols.get(id) {
+                self.cells[moved.row][moved.column] = Cell::Symbol(id);
+            }
+        }
+
+        let mut x = span.start;
+        for run in text.chunk_by(|a, b| a.is_ascii_digit() && b.is_ascii_digit()) {
+            let columns = x..x + run.len();
+            x = columns.end;
+            if run[0].is_ascii_digit() {
+                let value = run.iter().collect::<String>().parse().unwrap();
+                self.cells[row][columns.clone()].fill(Cell::Number(self.numbers.len()));
+                self.numbers.push(Number {
+                    value,
+                    row,
+                    columns,
+                });
+            } else if run[0] != '.' {
+                self.cells[row][columns.start] = Cell::Symbol(self.symbols.len());
+                self.symbols.push(Symbol {
+                    symbol: run[0],
+                    row,
+                    column: columns.start,
+                });
+            }
+        }
+
+        span
+    }
+
+    /// The cells around `rows` by `columns`, including the block itself.
+    fn around(&self, rows: Range<usize>, columns: Range<usize>) -> impl Iterator<Item = Cell> + '_ {
+        let rows = rows.start.saturating_sub(1)..rows.end + 1;
+        let columns = columns.start.saturating_sub(1)..columns.end + 1;
+        rows.flat_map(move |y| columns.clone().map(move |x| self.cell(y, x)))
+    }
+
+    /// The ids of the numbers touching symbol `id`, including diagonally.
+    #[must_use]
+    pub fn adjacent_numbers(&self, id: usize) -> Vec<usize> {
+        let symbol = &self.symbols[id];
+        let mut found = vec![];
+        for cell in self.around(symbol.row..symbol.row + 1, symbol.column..symbol.column + 1) {
+            if let Cell::Number(n) = cell {
+                if !found.contains(&n) {
+                    found.push(n);
+                }
+            }
+        }
+        found
+    }
+
+    /// The ids of the symbols touching number `id`, including diagonally.
+    #[must_use]
+    pub fn adjacent_symbols(&self, id: usize) -> Vec<usize> {
+        let number = &self.numbers[id];
+        self.around(number.row..number.row + 1, number.columns.clone())
+            .filter_map(|cell| match cell {
+                Cell::Symbol(s) => Some(s),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether number `id` is a part number, touching at least one symbol.
+    #[must_use]
+    pub fn is_part(&self, id: usize) -> bool {
+        let number = &self.numbers[id];
+        self.around(number.row..number.row + 1, number.columns.clone())
+            .any(|cell| matches!(cell, Cell::Symbol(_)))
+    }
+}
+
+impl Display for Schematic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for column in 0..self.width(row) {
+                write!(f, "{}", self.char_at(row, column))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn multi_byte_columns() {
+        let schematic = Schematic::parse("€.5\n..#");
+
+        assert_eq!(schematic.numbers()[0].columns, 2..3);
+        assert_eq!(
+            schematic.symbols()[0],
+            Symbol {
+                symbol: '€',
+                row: 0,
+                column: 0
+            }
+        );
+        assert!(schematic.is_part(0));
+    }
+
+    #[test]
+    fn unicode_symbols() {
+        assert_eq!(crate::solve_a("7..\n.→.\n..🦀8\n§..."), 15);
+
+        let rule = crate::GearRule {
+            symbols: vec!['⚙'],
+            ..crate::GearRule::part_b()
+        };
+        assert_eq!(crate::solve_b_with("2.§\n⚙3.", &rule), 6);
+    }
+
+    #[test]
+    fn adjacency() {
+        let schematic = Schematic::parse(INPUT);
+        let values = |ids: Vec<usize>| {
+            ids.into_iter()
+                .map(|id| schematic.numbers()[id].value)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(schematic.numbers().len(), 10);
+        assert_eq!(
+            schematic.numbers()[2],
+            Number {
+                value: 35,
+                row: 2,
+                columns: 2..4
+            }
+        );
+        assert_eq!(values(schematic.adjacent_numbers(0)), vec![467, 35]);
+        assert_eq!(values(schematic.adjacent_numbers(5)), vec![755, 598]);
+        assert_eq!(schematic.adjacent_symbols(3), vec![1]);
+        assert!(!schematic.is_part(1));
+        assert!(!schematic.is_part(5));
+    }
+}
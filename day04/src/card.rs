@@ -0,0 +1,214 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::parse_line;
+
+/// A scratchcard: its id, its winning numbers and the numbers picked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub picked: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCardError(pub String);
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid card '{}'", self.0)
+    }
+}
+
+impl Error for ParseCardError {}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, winning, picked) = parse_line(s).ok_or_else(|| ParseCardError(s.to_owned()))?;
+        Ok(Self {
+            id,
+            winning,
+            picked,
+        })
+    }
+}
+
+impl Card {
+    /// The number of picked numbers that are winning numbers.
+    #[must_use]
+    pub fn matches(&self) -> usize {
+        let winning: HashSet<u32> = self.winning.iter().copied().collect();
+        self.picked.iter().filter(|p| winning.contains(p)).count()
+    }
+}
+
+/// Every card in an input, in the order given.
+pub fn cards(input: &str) -> Result<Vec<Card>, ParseCardError> {
+    input.lines().map(str::parse).collect()
+}
+
+/// One of a card's two lists of numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum List {
+    Winning,
+    Picked,
+}
+
+/// Something about a table of cards that the puzzle doesn't expect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// A card with a lower id than the card before it.
+    OutOfOrder { id: u32, previous: u32 },
+    /// An id given to more than one card.
+    DuplicateId { id: u32 },
+    /// An id between 1 and the highest id that no card has.
+    MissingId { id: u32 },
+    /// A number listed more than once in one of a card's lists.
+    DuplicateNumber { id: u32, list: List, number: u32 },
+    /// A card whose lists are not the lengths of the first card's.
+    Lengths {
+        id: u32,
+        winning: usize,
+        picked: usize,
+        expected: (usize, usize),
+    },
+}
+
+impl Display for List {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Winning => "winning",
+            Self::Picked => "picked",
+        })
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfOrder { id, previous } => write!(f, "card {id} comes after card {previous}"),
+            Self::DuplicateId { id } => write!(f, "more than one card {id}"),
+            Self::MissingId { id } => write!(f, "no card {id}"),
+            Self::DuplicateNumber { id, list, number } => {
+                write!(
+                    f,
+                    "card {id} has {number} more than once in its {list} numbers"
+                )
+            }
+            Self::Lengths {
+                id,
+                winning,
+                picked,
+                expected: (w, p),
+            } => write!(
+                f,
+                "card {id} has {winning} winning and {picked} picked numbers, not {w} and {p}"
+            ),
+        }
+    }
+}
+
+/// Every problem with `cards`, card by card.
+#[must_use]
+pub fn validate(cards: &[Card]) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut ids = BTreeSet::new();
+    let expected = cards.first().map(|c| (c.winning.len(), c.picked.len()));
+
+    for (i, card) in cards.iter().enumerate() {
+        let id = card.id;
+        match i.checked_sub(1).map(|i| cards[i].id) {
+            Some(previous) if id < previous => problems.push(Problem::OutOfOrder { id, previous }),
+            _ => {}
+        }
+        if !ids.insert(id) {
+            problems.push(Problem::DuplicateId { id });
+        }
+
+        for (list, numbers) in [(List::Winning, &card.winning), (List::Picked, &card.picked)] {
+            let mut seen = HashSet::new();
+            let mut reported = HashSet::new();
+            for &number in numbers {
+                if !seen.insert(number) && reported.insert(number) {
+                    problems.push(Problem::DuplicateNumber { id, list, number });
+                }
+            }
+        }
+
+        if let Some(expected) = expected.filter(|&e| e != (card.winning.len(), card.picked.len())) {
+            problems.push(Problem::Lengths {
+                id,
+                winning: card.winning.len(),
+                picked: card.picked.len(),
+                expected,
+            });
+        }
+    }
+
+    let highest = ids.last().copied().unwrap_or(0);
+    problems.extend(
+        (1..highest)
+            .filter(|id| !ids.contains(id))
+            .map(|id| Problem::MissingId { id }),
+    );
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_id() {
+        let card: Card = "Card  12: 41 48 | 83 86  6".parse().unwrap();
+        assert_eq!(
+            card,
+            Card {
+                id: 12,
+                winning: vec![41, 48],
+                picked: vec![83, 86, 6]
+            }
+        );
+        assert_eq!(
+            "Card x: 1 | 2".parse::<Card>(),
+            Err(ParseCardError("Card x: 1 | 2".to_owned()))
+        );
+        assert_eq!(
+            "Card 1: 1 2 | 1 9 junk".parse::<Card>(),
+            Err(ParseCardError("Card 1: 1 2 | 1 9 junk".to_owned()))
+        );
+    }
+
+    #[test]
+    fn problems() {
+        let cards = cards(
+            "Card 1: 1 2 | 3 4 5
+Card 4: 1 1 | 3 4 3
+Card 2: 1 2 | 3 4
+Card 2: 1 2 | 3 4 5",
+        )
+        .unwrap();
+
+        assert_eq!(
+            validate(&cards)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "card 4 has 1 more than once in its winning numbers",
+                "card 4 has 3 more than once in its picked numbers",
+                "card 2 comes after card 4",
+                "card 2 has 2 winning and 2 picked numbers, not 2 and 3",
+                "more than one card 2",
+                "no card 3",
+            ]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::{collections::BTreeMap, error::Error, fmt::Display};
+
+use num_traits::{CheckedAdd, One, Zero};
+
+/// A type to count copies of cards in, such as `u64`, `u128` or
+/// `num_bigint::BigUint`.
+pub trait Counter: Clone + PartialEq + Zero + One + CheckedAdd {}
+
+impl<T: Clone + PartialEq + Zero + One + CheckedAdd> Counter for T {}
+
+/// What to do with copies won of cards past the end of the table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PastEnd {
+    /// Drop them, as the puzzle does.
+    #[default]
+    Truncate,
+    /// Give them to the cards from the lowest id on. Those cards have
+    /// already been scored, so the copies are counted but win nothing.
+    Wrap,
+    /// Stop with an error naming the card.
+    Error,
+}
+
+/// How copies of cards are won: each card wins one copy of each of the cards
+/// that follow it in order of id, as many as it has matches, for every copy
+/// of it held. Cards are scored in order of id, and ids no card has are
+/// skipped over, so the copies go to the next cards there are.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cascade {
+    pub past_end: PastEnd,
+    /// The most cards after its own that a card can win copies of.
+    pub window: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CascadeError {
+    /// The copies of a card, or the total up to it, didn't fit the counter.
+    Overflow { card: u32 },
+    /// A card won copies of cards past the one with the highest id.
+    PastEnd { card: u32, past_end: usize },
+    /// More than one card has the id.
+    DuplicateId { card: u32 },
+}
+
+impl Display for CascadeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overflow { card } => write!(f, "too many copies of card {card} to count"),
+            Self::PastEnd { card, past_end } => {
+                write!(f, "card {card} wins {past_end} cards past the end")
+            }
+            Self::DuplicateId { card } => write!(f, "more than one card {card}"),
+        }
+    }
+}
+
+impl Error for CascadeError {}
+
+impl Cascade {
+    /// How many of each card are held once every card has been scored, by
+    /// id, given the id of each card and its number of matches.
+    pub fn copies<T: Counter>(
+        &self,
+        matches: &[(u32, usize)],
+    ) -> Result<BTreeMap<u32, T>, CascadeError> {
+        self.run(matches, |_, _, _| {})
+    }
+
+    /// Works out the copies, calling `won` with the winning card, the card
+    /// won and how many copies of it were won each time a card is scored.
+    pub(crate) fn run<T: Counter>(
+        &self,
+        matches: &[(u32, usize)],
+        mut won: impl FnMut(u32, u32, &T),
+    ) -> Result<BTreeMap<u32, T>, CascadeError> {
+        let mut by_id = matches.to_vec();
+        by_id.sort_unstable();
+        if let Some(w) = by_id.windows(2).find(|w| w[0].0 == w[1].0) {
+            return Err(CascadeError::DuplicateId { card: w[0].0 });
+        }
+        let Some(last) = by_id.len().checked_sub(1) else {
+            return Ok(BTreeMap::new());
+        };
+
+        // cards by their place in order of id, so gaps in the ids don't count
+        let mut copies = vec![T::one(); by_id.len()];
+        for (i, &(card, m)) in by_id.iter().enumerate() {
+            let m = self.window.map_or(m, |window| m.min(window));
+            let past_end = (i + m).saturating_sub(last);
+            let wins = match self.past_end {
+                _ if past_end == 0 => m,
+                PastEnd::Truncate => m - past_end,
+                PastEnd::Wrap => m.min(last),
+                PastEnd::Error => return Err(CascadeError::PastEnd { card, past_end }),
+            };
+
+            let held = copies[i].clone();
+            for target in i + 1..=i + wins {
+                let target = if target > last {
+                    target - last - 1
+                } else {
+                    target
+                };
+                let id = by_id[target].0;
+                copies[target] = copies[target]
+                    .checked_add(&held)
+                    .ok_or(CascadeError::Overflow { card: id })?;
+                won(card, id, &held);
+            }
+        }
+
+        Ok(by_id.into_iter().map(|(id, _)| id).zip(copies).collect())
+    }
+
+    /// The total number of cards held once every card has been scored.
+    pub fn total<T: Counter>(&self, matches: &[(u32, usize)]) -> Result<T, CascadeError> {
+        self.copies::<T>(matches)?
+            .iter()
+            .try_fold(T::zero(), |total, (&card, c)| {
+                total.checked_add(c).ok_or(CascadeError::Overflow { card })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    /// Numbers `matches` with ids from 1.
+    fn numbered(matches: &[usize]) -> Vec<(u32, usize)> {
+        (1..).zip(matches.iter().copied()).collect()
+    }
+
+    fn copies(cascade: &Cascade, matches: &[usize]) -> Result<Vec<u64>, CascadeError> {
+        Ok(cascade.copies(&numbered(matches))?.into_values().collect())
+    }
+
+    #[test]
+    fn past_end_policies() {
+        let matches = [0, 2, 3];
+        let cascade = |past_end| Cascade {
+            past_end,
+            window: None,
+        };
+
+        assert_eq!(
+            copies(&cascade(PastEnd::Truncate), &matches),
+            Ok(vec![1, 1, 2])
+        );
+        assert_eq!(copies(&cascade(PastEnd::Wrap), &matches), Ok(vec![4, 3, 2]));
+        assert_eq!(
+            copies(&cascade(PastEnd::Error), &matches),
+            Err(CascadeError::PastEnd {
+                card: 2,
+                past_end: 1
+            })
+        );
+    }
+
+    #[test]
+    fn window() {
+        let cascade = Cascade {
+            past_end: PastEnd::Error,
+            window: Some(1),
+        };
+        assert_eq!(copies(&cascade, &[4, 4, 4, 0]), Ok(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn keyed_by_id() {
+        let cascade = Cascade::default();
+        let matches = [(7, 0), (2, 3), (5, 1), (3, 1)];
+
+        assert_eq!(
+            cascade.copies::<u64>(&matches),
+            Ok(BTreeMap::from([(2, 1), (3, 2), (5, 4), (7, 6)]))
+        );
+        assert_eq!(
+            cascade.copies::<u64>(&[(1, 0), (1, 0)]),
+            Err(CascadeError::DuplicateId { card: 1 })
+        );
+    }
+
+    #[test]
+    fn counters() {
+        // every card wins a copy of every later one, so card n has 2^(n-1)
+        let matches = numbered(&(0..130).rev().collect::<Vec<_>>());
+        let cascade = Cascade::default();
+
+        assert_eq!(
+            cascade.total::<u64>(&matches),
+            Err(CascadeError::Overflow { card: 65 })
+        );
+        assert_eq!(cascade.total::<u128>(&matches[2..]), Ok(u128::MAX));
+        assert_eq!(
+            cascade.total::<BigUint>(&matches),
+            Ok((BigUint::from(1_u8) << 130) - 1_u8)
+        );
+    }
+}
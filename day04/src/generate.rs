@@ -0,0 +1,77 @@
+use rand::{rngs::StdRng, seq::index::sample, seq::SliceRandom, Rng, SeedableRng};
+
+const WINNING: usize = 10;
+const PICKED: usize = 25;
+
+/// Generates `size` scratchcards, with the answers to both parts.
+///
+/// No card wins copies of cards past the end of the table. The part b answer
+/// is only given if it fits the `u64` that `solve_b` counts in.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u64>, Option<u64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let width = size.to_string().len();
+
+    let mut lines = vec![];
+    let mut matches = vec![];
+
+    for id in 1..=size {
+        // mostly losing cards, as in real inputs, so the copies stay countable
+        let m = if rng.gen_bool(0.7) {
+            0
+        } else {
+            rng.gen_range(1..=WINNING / 2).min(size - id)
+        };
+
+        // pick from 1..=99 so every number fits the two character columns
+        let mut numbers = sample(&mut rng, 99, WINNING + PICKED - m)
+            .into_iter()
+            .map(|n| n + 1)
+            .collect::<Vec<_>>();
+        let winning = numbers[..WINNING].to_vec();
+        numbers.drain(..WINNING - m);
+        numbers.shuffle(&mut rng);
+
+        let list = |l: &[usize]| l.iter().map(|n| format!("{n:>2}")).collect::<Vec<_>>();
+        lines.push(format!(
+            "Card {id:>width$}: {} | {}",
+            list(&winning).join(" "),
+            list(&numbers).join(" ")
+        ));
+        matches.push(m);
+    }
+
+    let sum_a = matches
+        .iter()
+        .map(|&m| if m == 0 { 0 } else { 1 << (m - 1) })
+        .sum();
+
+    // saturating, as a total too big for a u128 is too big for a u64 anyway
+    let mut copies = vec![1_u128; size];
+    for (i, &m) in matches.iter().enumerate() {
+        for j in i + 1..=i + m {
+            copies[j] = copies[j].saturating_add(copies[i]);
+        }
+    }
+
+    let sum_b = copies
+        .iter()
+        .fold(0_u128, |sum, &c| sum.saturating_add(c))
+        .try_into()
+        .ok();
+
+    (lines.join("\n"), Some(sum_a), sum_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 200);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+}
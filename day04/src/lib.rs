@@ -4,18 +4,32 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
-use std::collections::HashSet;
+mod card;
+mod cascade;
+mod generate;
+mod provenance;
+mod scoring;
+
+pub use card::{cards, validate, Card, List, ParseCardError, Problem};
+pub use cascade::{Cascade, CascadeError, Counter, PastEnd};
+pub use generate::generate;
+pub use provenance::{Origin, Provenance};
+pub use scoring::{scoring, Doubling, Fibonacci, Linear, Scoring, ScoringError, Table};
+
 use winnow::{
     ascii::{dec_uint, multispace0},
     combinator::{delimited, preceded, repeat},
-    PResult, Parser,
+    error::ContextError,
+    Parser,
 };
 
-fn parse_line(mut input: &str) -> PResult<(u32, Vec<u32>, Vec<u32>)> {
+/// The id, winning numbers and picked numbers of a card, if `input` is
+/// exactly one card.
+pub(crate) fn parse_line(input: &str) -> Option<(u32, Vec<u32>, Vec<u32>)> {
     (
         delimited(
             "Card",
-            delimited(multispace0, dec_uint::<_, u32, _>, multispace0),
+            delimited(multispace0, dec_uint::<_, u32, ContextError>, multispace0),
             ':',
         ),
         repeat(
@@ -30,56 +44,57 @@ fn parse_line(mut input: &str) -> PResult<(u32, Vec<u32>, Vec<u32>)> {
             ),
         ),
     )
-        .parse_next(&mut input)
+        .parse(input)
+        .ok()
 }
 
+/// The id of each card and its number of [matches](Card::matches). Both
+/// parts work from these, so a caller solving both can find them once and
+/// pass them to [`points`] and [`Cascade::total`].
 #[must_use]
-pub fn solve_a(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|l| parse_line(l).unwrap())
-        .map(|(_, winning, picked)| {
-            let winning: HashSet<u32> = winning.iter().copied().collect();
-            match picked.iter().filter(|p| winning.contains(p)).count() {
-                0 => 0,
-                n => 2_u32.pow((n - 1).try_into().unwrap()),
-            }
-        })
-        .sum()
+pub fn matches(input: &str) -> Vec<(u32, usize)> {
+    cards(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .iter()
+        .map(|card| (card.id, card.matches()))
+        .collect()
+}
+
+/// The total points of cards with `matches` under `scoring`. Like the
+/// scores of single cards, the total stops at `u64::MAX`.
+pub fn points(matches: &[(u32, usize)], scoring: &(impl Scoring + ?Sized)) -> u64 {
+    matches
+        .iter()
+        .fold(0, |total, &(_, m)| total.saturating_add(scoring.points(m)))
 }
 
+/// Describes each [`Problem`] with the cards.
 #[must_use]
-pub fn solve_b(input: &str) -> u32 {
-    let winning_games: Vec<usize> = input
-        .lines()
-        .map(|l| parse_line(l).unwrap())
-        .map(|(_, winning, picked)| {
-            let winning_numbers: HashSet<u32> = winning.iter().copied().collect();
-            let winning_count = picked
-                .iter()
-                .filter(|p| winning_numbers.contains(p))
-                .count();
-
-            winning_count
-        })
-        .collect();
-
-    let line_count = input.lines().count();
-    let mut game_counts = vec![1; line_count];
-    let mut result = 0;
-    for i in 0..line_count {
-        let count = game_counts.get(i).copied().unwrap();
-        for game in game_counts
-            .iter_mut()
-            .skip(i + 1)
-            .take(*winning_games.get(i).unwrap())
-        {
-            *game += count;
-        }
-        result += count;
+pub fn check(input: &str) -> Vec<String> {
+    match cards(input) {
+        Ok(cards) => validate(&cards).iter().map(ToString::to_string).collect(),
+        Err(e) => vec![e.to_string()],
     }
+}
+
+#[must_use]
+pub fn solve_a(input: &str) -> u64 {
+    solve_a_with(input, &Doubling)
+}
 
-    result
+/// The total points of the cards under `scoring`.
+pub fn solve_a_with(input: &str, scoring: &(impl Scoring + ?Sized)) -> u64 {
+    points(&matches(input), scoring)
+}
+
+#[must_use]
+pub fn solve_b(input: &str) -> u64 {
+    solve_b_with(input, &Cascade::default()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// The total number of cards held under `cascade`, counted in `T`.
+pub fn solve_b_with<T: Counter>(input: &str, cascade: &Cascade) -> Result<T, CascadeError> {
+    cascade.total(&matches(input))
 }
 
 #[cfg(test)]
@@ -111,4 +126,13 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
     fn solution_b() {
         assert_eq!(solve_b(include_str!("input.txt")), 5_921_508);
     }
+
+    #[test]
+    fn points_saturate() {
+        assert_eq!(
+            points(&[(1, 1), (2, 1)], &Table(vec![0, u64::MAX])),
+            u64::MAX
+        );
+        assert_eq!(points(&[(1, 70), (2, 70)], &Doubling), u64::MAX);
+    }
 }
@@ -0,0 +1,184 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+};
+
+use crate::{Cascade, CascadeError, Counter};
+
+/// Where the copies of one card came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin<T> {
+    /// Every copy held, counting the original.
+    pub copies: T,
+    /// How many copies each card won of this one, by id.
+    pub from: BTreeMap<u32, T>,
+}
+
+/// Where the copies of every card came from, by id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance<T> {
+    pub cards: BTreeMap<u32, Origin<T>>,
+}
+
+impl Cascade {
+    /// The copies of each card, as [`Cascade::copies`] counts them, with how
+    /// many were won from each other card.
+    pub fn provenance<T: Counter>(
+        &self,
+        matches: &[(u32, usize)],
+    ) -> Result<Provenance<T>, CascadeError> {
+        let mut from = BTreeMap::<u32, BTreeMap<u32, T>>::new();
+        let copies = self.run(matches, |winner, won, held: &T| {
+            from.entry(won).or_default().insert(winner, held.clone());
+        })?;
+
+        let cards = copies
+            .into_iter()
+            .map(|(id, copies)| {
+                let from = from.remove(&id).unwrap_or_default();
+                (id, Origin { copies, from })
+            })
+            .collect();
+        Ok(Provenance { cards })
+    }
+}
+
+impl<T: Counter> Provenance<T> {
+    /// The total number of cards held, as part b counts it, or `None` if it
+    /// doesn't fit a `T`.
+    #[must_use]
+    pub fn total(&self) -> Option<T> {
+        self.cards
+            .values()
+            .try_fold(T::zero(), |total, o| total.checked_add(&o.copies))
+    }
+}
+
+impl<T: Counter + Display> Provenance<T> {
+    /// Where card `id`'s copies came from, in words, such as "card 4 has 8
+    /// copies: 1 original plus 1 from card 1, 2 from card 2, 4 from card 3".
+    #[must_use]
+    pub fn describe(&self, id: u32) -> Option<String> {
+        let origin = self.cards.get(&id)?;
+        let noun = if origin.copies.is_one() {
+            "copy"
+        } else {
+            "copies"
+        };
+        let mut out = format!("card {id} has {} {noun}: 1 original", origin.copies);
+        for (i, (card, copies)) in origin.from.iter().enumerate() {
+            let join = if i == 0 { " plus" } else { "," };
+            write!(out, "{join} {copies} from card {card}").unwrap();
+        }
+        Some(out)
+    }
+
+    /// The cards in Graphviz's DOT language, with an edge from each card to
+    /// every card it won copies of, labelled with how many. Unless copies
+    /// wrap past the end, this is a DAG.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut out = "digraph provenance {\n".to_owned();
+        for (id, origin) in &self.cards {
+            writeln!(out, "  c{id} [label=\"card {id}\\n{}\"];", origin.copies).unwrap();
+        }
+        for (id, origin) in &self.cards {
+            for (card, copies) in &origin.from {
+                writeln!(out, "  c{card} -> c{id} [label=\"{copies}\"];").unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// The cards as JSON: a list of objects with the card's `id`, its
+    /// `copies` and a `from` object mapping ids to the copies they won.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut out = "[".to_owned();
+        for (i, (id, origin)) in self.cards.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            write!(
+                out,
+                "{{\"id\":{id},\"copies\":{},\"from\":{{",
+                origin.copies
+            )
+            .unwrap();
+            for (j, (card, copies)) in origin.from.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                write!(out, "\"{card}\":{copies}").unwrap();
+            }
+            out.push_str("}}");
+        }
+        out.push(']');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{matches, solve_b};
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn example() {
+        let provenance = Cascade::default()
+            .provenance::<u64>(&matches(INPUT))
+            .unwrap();
+
+        assert_eq!(provenance.total(), Some(30));
+        assert_eq!(
+            provenance.describe(4).unwrap(),
+            "card 4 has 8 copies: 1 original plus 1 from card 1, 2 from card 2, 4 from card 3"
+        );
+        assert_eq!(
+            provenance.describe(1).unwrap(),
+            "card 1 has 1 copy: 1 original"
+        );
+    }
+
+    #[test]
+    fn matches_solve_b() {
+        let input = include_str!("input.txt");
+        let provenance = Cascade::default()
+            .provenance::<u64>(&matches(input))
+            .unwrap();
+
+        assert_eq!(provenance.total(), Some(solve_b(input)));
+        for origin in provenance.cards.values() {
+            assert_eq!(origin.copies, 1 + origin.from.values().sum::<u64>());
+        }
+    }
+
+    #[test]
+    fn exports() {
+        let provenance = Cascade::default()
+            .provenance::<u64>(&[(1, 1), (2, 0)])
+            .unwrap();
+
+        assert_eq!(
+            provenance.to_dot(),
+            "digraph provenance {
+  c1 [label=\"card 1\\n1\"];
+  c2 [label=\"card 2\\n2\"];
+  c1 -> c2 [label=\"1\"];
+}
+"
+        );
+        assert_eq!(
+            provenance.to_json(),
+            r#"[{"id":1,"copies":1,"from":{}},{"id":2,"copies":2,"from":{"1":1}}]"#
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::{error::Error, fmt::Display};
+
+/// How many points a card is worth for its number of matches.
+pub trait Scoring {
+    fn points(&self, matches: usize) -> u64;
+}
+
+/// One point for the first match, doubled for each match after it, as the
+/// puzzle scores cards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Doubling;
+
+/// A point for each match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Linear;
+
+/// The Fibonacci number for the matches: 1, 1, 2, 3, 5 and so on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fibonacci;
+
+/// The points for each number of matches, from none up. Cards with more
+/// matches than the table lists score its last entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table(pub Vec<u64>);
+
+impl Scoring for Doubling {
+    fn points(&self, matches: usize) -> u64 {
+        match matches {
+            0 => 0,
+            n => u32::try_from(n - 1)
+                .ok()
+                .and_then(|shift| 1_u64.checked_shl(shift))
+                .unwrap_or(u64::MAX),
+        }
+    }
+}
+
+impl Scoring for Linear {
+    fn points(&self, matches: usize) -> u64 {
+        matches as u64
+    }
+}
+
+impl Scoring for Fibonacci {
+    fn points(&self, matches: usize) -> u64 {
+        let (mut a, mut b) = (0_u64, 1_u64);
+        for _ in 0..matches {
+            (a, b) = (b, a.saturating_add(b));
+        }
+        a
+    }
+}
+
+impl Scoring for Table {
+    fn points(&self, matches: usize) -> u64 {
+        self.0.get(matches).or(self.0.last()).copied().unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoringError(pub String);
+
+impl Display for ScoringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for ScoringError {}
+
+/// The scoring called `name`: `doubling`, `linear`, `fibonacci`, or `table:`
+/// followed by the points for each number of matches, such as
+/// `table:0,1,3,6`.
+pub fn scoring(name: &str) -> Result<Box<dyn Scoring>, ScoringError> {
+    match name {
+        "doubling" => Ok(Box::new(Doubling)),
+        "linear" => Ok(Box::new(Linear)),
+        "fibonacci" => Ok(Box::new(Fibonacci)),
+        _ => {
+            let Some(points) = name.strip_prefix("table:") else {
+                return Err(ScoringError(format!("unknown scoring '{name}'")));
+            };
+            let points = points
+                .split(',')
+                .map(|p| p.trim().parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| ScoringError(format!("invalid points table '{points}'")))?;
+            Ok(Box::new(Table(points)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(scoring: &dyn Scoring) -> Vec<u64> {
+        (0..7).map(|m| scoring.points(m)).collect()
+    }
+
+    #[test]
+    fn built_in() {
+        assert_eq!(points(&Doubling), vec![0, 1, 2, 4, 8, 16, 32]);
+        assert_eq!(points(&Linear), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(points(&Fibonacci), vec![0, 1, 1, 2, 3, 5, 8]);
+        assert_eq!(points(&Table(vec![0, 5, 7])), vec![0, 5, 7, 7, 7, 7, 7]);
+        assert_eq!(Doubling.points(100), u64::MAX);
+    }
+
+    #[test]
+    fn by_name() {
+        assert_eq!(
+            points(&*scoring("table:0, 2,9").unwrap()),
+            points(&Table(vec![0, 2, 9]))
+        );
+        assert_eq!(points(&*scoring("fibonacci").unwrap()), points(&Fibonacci));
+        assert_eq!(
+            scoring("squares").err(),
+            Some(ScoringError("unknown scoring 'squares'".to_owned()))
+        );
+        assert_eq!(
+            scoring("table:1,x").err(),
+            Some(ScoringError("invalid points table '1,x'".to_owned()))
+        );
+    }
+}
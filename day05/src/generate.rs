@@ -0,0 +1,93 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+const STAGES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+const DOMAIN: u64 = 1 << 32;
+
+/// Generates an almanac with `size` seed ranges, at least one, with the
+/// answer to part a.
+///
+/// Each map shuffles a random partition of `0..2^32` and leaves a few of its
+/// ranges out, so those ranges map to themselves.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u64>, Option<u64>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let seeds = (0..size)
+        .flat_map(|_| {
+            let start = rng.gen_range(0..DOMAIN / 2);
+            [start, rng.gen_range(1..DOMAIN / 16)]
+        })
+        .collect::<Vec<_>>();
+
+    let mut locations = seeds.clone();
+    let mut sections = vec![format!(
+        "seeds: {}",
+        seeds
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    )];
+
+    for stage in STAGES {
+        let mut cuts = (0..rng.gen_range(10..40))
+            .map(|_| rng.gen_range(1..DOMAIN))
+            .collect::<Vec<_>>();
+        cuts.extend([0, DOMAIN]);
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let mut ranges = cuts.windows(2).map(|w| w[0]..w[1]).collect::<Vec<_>>();
+        ranges.shuffle(&mut rng);
+
+        let mut dest = 0;
+        let mut lines = vec![format!("{stage} map:")];
+        let mut mapped = vec![];
+        for source in ranges {
+            let len = source.end - source.start;
+            if rng.gen_bool(0.9) {
+                lines.push(format!("{dest} {} {len}", source.start));
+                mapped.push((dest, source));
+            }
+            dest += len;
+        }
+
+        for l in &mut locations {
+            if let Some((dest, source)) = mapped.iter().find(|(_, s)| s.contains(l)) {
+                *l = *l - source.start + dest;
+            }
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    (sections.join("\n\n"), locations.into_iter().min(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve_a;
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, _) = generate(1, 10);
+        assert_eq!(Some(solve_a(&input)), a);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, _) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+    }
+}
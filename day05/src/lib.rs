@@ -2,15 +2,22 @@
 #![warn(clippy::style)]
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::missing_panics_doc)]
+#![allow(clippy::single_range_in_vec_init)]
 #![warn(clippy::use_self)]
 
+mod generate;
+mod piecewise;
+
+pub use generate::generate;
+pub use piecewise::{PiecewiseMap, Segment};
+
 use std::ops::Range;
 
 use itertools::Itertools;
 use winnow::{
     ascii::{dec_uint, line_ending},
     combinator::{preceded, repeat, separated},
-    token::take_until1,
+    token::take_until,
     PResult, Parser,
 };
 
@@ -20,7 +27,7 @@ fn parse_seeds_a(input: &mut &str) -> PResult<Vec<u64>> {
 
 fn parse_map(input: &mut &str) -> PResult<Vec<(u64, Range<u64>)>> {
     preceded(
-        (line_ending, line_ending, take_until1("map:"), "map:"),
+        (line_ending, line_ending, take_until(1.., "map:"), "map:"),
         repeat(
             1..,
             preceded(
@@ -39,124 +46,39 @@ fn parse_map(input: &mut &str) -> PResult<Vec<(u64, Range<u64>)>> {
     .parse_next(input)
 }
 
-#[must_use]
-pub fn solve_a(mut input: &str) -> u64 {
+/// The seeds and the map from seeds to locations that the stages compose to.
+fn parse(mut input: &str) -> (Vec<u64>, PiecewiseMap) {
     let parse_input = &mut input;
     let seeds = parse_seeds_a.parse_next(parse_input).unwrap();
     let location_maps: Vec<Vec<_>> = repeat(1.., parse_map).parse(parse_input).unwrap();
 
-    seeds
-        .into_iter()
-        .map(|s| map_seed(s, &location_maps))
-        .min()
-        .unwrap()
+    let location = location_maps
+        .iter()
+        .fold(PiecewiseMap::identity(), |map, stage| {
+            map.then(&PiecewiseMap::stage(stage))
+        });
+    (seeds, location)
 }
 
 #[must_use]
-pub fn solve_b(mut input: &str) -> u64 {
-    let parse_input = &mut input;
-    let seeds = parse_seeds_a
-        .parse_next(parse_input)
-        .unwrap()
-        .into_iter()
-        .tuples()
-        .map(|(a, b)| a..(a + b))
-        .collect_vec();
+pub fn solve_a(input: &str) -> u64 {
+    let (seeds, location) = parse(input);
 
-    let location_maps: Vec<Vec<_>> = repeat(1.., parse_map).parse(parse_input).unwrap();
+    seeds.into_iter().map(|s| location.get(s)).min().unwrap()
+}
 
-    println!("{seeds:?}");
+#[must_use]
+pub fn solve_b(input: &str) -> u64 {
+    let (seeds, location) = parse(input);
 
-    map_seed_range(seeds, &location_maps)
+    seeds
         .into_iter()
-        .map(|r| r.start)
+        .tuples()
+        .filter_map(|(a, b)| location.min_over(a..(a + b)))
         .min()
         .unwrap()
 }
 
-fn map_seed(mut seed: u64, location_maps: &[Vec<(u64, Range<u64>)>]) -> u64 {
-    for map_list in location_maps {
-        seed = if let Some((dest, source)) =
-            map_list.iter().find(|(_, source)| source.contains(&seed))
-        {
-            seed - source.start + dest
-        } else {
-            seed
-        };
-    }
-    seed
-}
-
-fn map_seed_range(
-    mut seeds: Vec<Range<u64>>,
-    location_maps: &[Vec<(u64, Range<u64>)>],
-) -> Vec<Range<u64>> {
-    for map_list in location_maps {
-        let mut processed = vec![];
-
-        while let Some(seed) = seeds.pop() {
-            let mut any_hit = false;
-            for (dest, source) in map_list {
-                let (misses, hit) = intersect(&seed, source, *dest);
-
-                if let Some(hit) = hit {
-                    processed.push(hit);
-                    seeds.extend(misses);
-                    any_hit = true;
-                    break;
-                }
-            }
-            if !any_hit {
-                processed.push(seed);
-            }
-        }
-
-        seeds = processed;
-    }
-
-    seeds
-}
-
-fn intersect(
-    seed: &Range<u64>,
-    source: &Range<u64>,
-    dest: u64,
-) -> (Vec<Range<u64>>, Option<Range<u64>>) {
-    // no intersection
-    if seed.end <= source.start || seed.start >= source.end {
-        return (vec![seed.clone()], None);
-    }
-
-    // bisect
-    if seed.start < source.start && seed.end > source.end {
-        return (
-            vec![seed.start..source.start, source.end..seed.end],
-            Some((dest)..(dest + source.end - source.start)),
-        );
-    }
-
-    // single overlap greater
-    if seed.start < source.start && seed.end <= source.end {
-        return (
-            vec![seed.start..source.start],
-            Some((dest)..(seed.end - source.start + dest)),
-        );
-    }
-    // single overlap less
-    if seed.start >= source.start && seed.end > source.end {
-        return (
-            vec![source.end..seed.end],
-            Some((seed.start - source.start + dest)..(source.end - source.start + dest)),
-        );
-    }
-
-    // contained
-    (
-        vec![],
-        Some((seed.start - source.start + dest)..(seed.end - source.start + dest)),
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,49 +134,4 @@ humidity-to-location map:
     fn solution_b() {
         assert_eq!(solve_b(include_str!("input.txt")), 47_909_639);
     }
-
-    #[test]
-    fn intersect_miss() {
-        assert_eq!(intersect(&(0..10), &(10..20), 100), (vec![0..10], None));
-    }
-
-    #[test]
-    fn intersect_match() {
-        assert_eq!(
-            intersect(&(10..20), &(10..20), 100),
-            (vec![], Some(100..110))
-        );
-    }
-
-    #[test]
-    fn intersect_bisect() {
-        assert_eq!(
-            intersect(&(0..30), &(10..20), 100),
-            (vec![0..10, 20..30], Some(100..110))
-        );
-    }
-
-    #[test]
-    fn intersect_inside() {
-        assert_eq!(
-            intersect(&(10..20), &(0..30), 100),
-            (vec![], Some(110..120))
-        );
-    }
-
-    #[test]
-    fn intersect_overlap_greater() {
-        assert_eq!(
-            intersect(&(0..20), &(10..30), 100),
-            (vec![0..10], Some(100..110))
-        );
-    }
-
-    #[test]
-    fn intersect_overlap_less() {
-        assert_eq!(
-            intersect(&(10..30), &(0..20), 100),
-            (vec![20..30], Some(110..120))
-        );
-    }
 }
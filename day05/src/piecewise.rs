@@ -0,0 +1,259 @@
+use std::ops::Range;
+
+/// A run of numbers mapped in order onto the numbers from `dest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub source: Range<u64>,
+    pub dest: u64,
+}
+
+impl Segment {
+    fn identity(source: Range<u64>) -> Self {
+        let dest = source.start;
+        Self { source, dest }
+    }
+
+    fn map(&self, x: u64) -> u64 {
+        self.dest + (x - self.source.start)
+    }
+
+    fn len(&self) -> u64 {
+        self.source.end - self.source.start
+    }
+}
+
+/// A map of every number below `u64::MAX` made of [`Segment`]s, sorted and
+/// with no gaps, so a number's segment can be binary searched for. No range
+/// can hold `u64::MAX` itself, so every map takes it to itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    /// The map taking every number to itself.
+    #[must_use]
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![Segment {
+                source: 0..u64::MAX,
+                dest: 0,
+            }],
+        }
+    }
+
+    /// One stage of an almanac, mapping each `(dest, source)` range onto the
+    /// numbers from `dest` and every other number to itself. Where ranges
+    /// overlap, the one listed first wins.
+    #[must_use]
+    pub fn stage(ranges: &[(u64, Range<u64>)]) -> Self {
+        // each range only takes the numbers no range before it has taken
+        let mut taken: Vec<Segment> = vec![];
+        for (dest, source) in ranges {
+            let mut free = vec![];
+            let mut at = source.start;
+            for t in taken
+                .iter()
+                .filter(|t| t.source.start < source.end && source.start < t.source.end)
+            {
+                if at < t.source.start {
+                    free.push(at..t.source.start);
+                }
+                at = at.max(t.source.end);
+            }
+            if at < source.end {
+                free.push(at..source.end);
+            }
+
+            taken.extend(free.into_iter().map(|free| Segment {
+                dest: dest + (free.start - source.start),
+                source: free,
+            }));
+            taken.sort_unstable_by_key(|t| t.source.start);
+        }
+
+        let mut segments = vec![];
+        let mut at = 0;
+        for segment in taken {
+            if at < segment.source.start {
+                push(&mut segments, Segment::identity(at..segment.source.start));
+            }
+            at = segment.source.end;
+            push(&mut segments, segment);
+        }
+        if at < u64::MAX {
+            push(&mut segments, Segment::identity(at..u64::MAX));
+        }
+
+        Self { segments }
+    }
+
+    #[must_use]
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The index of the segment holding `x`.
+    fn index(&self, x: u64) -> usize {
+        self.segments.partition_point(|s| s.source.end <= x)
+    }
+
+    #[must_use]
+    pub fn get(&self, x: u64) -> u64 {
+        self.segments.get(self.index(x)).map_or(x, |s| s.map(x))
+    }
+
+    /// The parts of the segments within `range`.
+    fn pieces(&self, range: Range<u64>) -> impl Iterator<Item = Segment> + '_ {
+        self.segments[self.index(range.start)..]
+            .iter()
+            .take_while(move |s| s.source.start < range.end)
+            .map(move |s| {
+                let start = s.source.start.max(range.start);
+                Segment {
+                    source: start..s.source.end.min(range.end),
+                    dest: s.map(start),
+                }
+            })
+            .filter(|p| !p.source.is_empty())
+    }
+
+    /// The ranges that `range` maps onto, in the order of the numbers they
+    /// come from.
+    #[must_use]
+    pub fn image(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        self.pieces(range)
+            .map(|p| p.dest..p.dest + p.len())
+            .collect()
+    }
+
+    /// The lowest number that anything in `range` maps to. Each segment
+    /// only needs checking at its start within the range.
+    #[must_use]
+    pub fn min_over(&self, range: Range<u64>) -> Option<u64> {
+        self.pieces(range).map(|p| p.dest).min()
+    }
+
+    /// The map taking each number through this map and then `next`.
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let mut segments = vec![];
+        for s in &self.segments {
+            let image = s.dest..s.dest.saturating_add(s.len());
+            for p in next.pieces(image.clone()) {
+                let start = s.source.start + (p.source.start - s.dest);
+                push(
+                    &mut segments,
+                    Segment {
+                        source: start..start + p.len(),
+                        dest: p.dest,
+                    },
+                );
+            }
+            // a number mapped to `u64::MAX` stays there
+            if image.end == u64::MAX && s.dest.checked_add(s.len()).is_none() {
+                let start = s.source.start + (u64::MAX - s.dest);
+                push(
+                    &mut segments,
+                    Segment {
+                        source: start..s.source.end,
+                        dest: u64::MAX,
+                    },
+                );
+            }
+        }
+        Self { segments }
+    }
+}
+
+/// Adds `segment` to the end of `segments`, joining it to the last one if
+/// it carries straight on from it.
+fn push(segments: &mut Vec<Segment>, segment: Segment) {
+    match segments.last_mut() {
+        Some(last)
+            if last.source.end == segment.source.start
+                && last.dest.checked_add(last.len()) == Some(segment.dest) =>
+        {
+            last.source.end = segment.source.end;
+        }
+        _ => segments.push(segment),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_miss() {
+        let stage = PiecewiseMap::stage(&[(100, 10..20)]);
+        assert_eq!(stage.image(0..10), vec![0..10]);
+    }
+
+    #[test]
+    fn stage_match() {
+        let stage = PiecewiseMap::stage(&[(100, 10..20)]);
+        assert_eq!(stage.image(10..20), vec![100..110]);
+    }
+
+    #[test]
+    fn stage_bisect() {
+        let stage = PiecewiseMap::stage(&[(100, 10..20)]);
+        assert_eq!(stage.image(0..30), vec![0..10, 100..110, 20..30]);
+        assert_eq!(stage.min_over(0..30), Some(0));
+        assert_eq!(stage.min_over(10..30), Some(20));
+    }
+
+    #[test]
+    fn stage_inside() {
+        let stage = PiecewiseMap::stage(&[(100, 0..30)]);
+        assert_eq!(stage.image(10..20), vec![110..120]);
+    }
+
+    #[test]
+    fn stage_overlap_greater() {
+        let stage = PiecewiseMap::stage(&[(100, 10..30)]);
+        assert_eq!(stage.image(0..20), vec![0..10, 100..110]);
+    }
+
+    #[test]
+    fn stage_overlap_less() {
+        let stage = PiecewiseMap::stage(&[(100, 0..20)]);
+        assert_eq!(stage.image(10..30), vec![110..120, 20..30]);
+    }
+
+    #[test]
+    fn stage_listed_first_wins() {
+        let stage = PiecewiseMap::stage(&[(100, 10..20), (200, 0..30)]);
+        assert_eq!(stage.get(5), 205);
+        assert_eq!(stage.get(15), 105);
+        assert_eq!(stage.get(25), 225);
+        assert_eq!(stage.image(0..30), vec![200..210, 100..110, 220..230]);
+    }
+
+    #[test]
+    fn top_of_range() {
+        let stage = PiecewiseMap::stage(&[(u64::MAX - 1, 5..7)]);
+        assert_eq!(stage.get(u64::MAX), u64::MAX);
+        assert_eq!(stage.get(6), u64::MAX);
+
+        let composed = stage.then(&PiecewiseMap::stage(&[(0, 10..20)]));
+        assert_eq!(composed.get(5), u64::MAX - 1);
+        assert_eq!(composed.get(6), u64::MAX);
+        assert_eq!(composed.get(7), 7);
+        assert_eq!(composed.get(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn composed() {
+        let a = PiecewiseMap::stage(&[(50, 98..100), (52, 50..98)]);
+        let b = PiecewiseMap::stage(&[(0, 15..52), (37, 52..54), (39, 0..15)]);
+        let ab = a.then(&b);
+
+        for x in 0..200 {
+            assert_eq!(ab.get(x), b.get(a.get(x)), "{x}");
+        }
+        assert_eq!(PiecewiseMap::identity().then(&a), a);
+        assert_eq!(a.then(&PiecewiseMap::identity()), a);
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The most races generated. Each time has two digits and each distance up
+/// to four, so joined for part b they still fit in a `u64`.
+const MAX_RACES: usize = 4;
+
+/// Generates `size` races, between one and four, with the answers to both
+/// parts.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u64>, Option<u64>) {
+    let size = size.clamp(1, MAX_RACES);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let races = (0..size)
+        .map(|_| {
+            let time: u64 = rng.gen_range(10..100);
+            // always beatable: the best hold time goes time^2 / 4
+            (time, rng.gen_range(time..time * time / 4))
+        })
+        .collect::<Vec<_>>();
+
+    let width = races
+        .iter()
+        .map(|(_, d)| d.to_string().len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let mut time_line = "Time:     ".to_owned();
+    let mut distance_line = "Distance: ".to_owned();
+    for (t, d) in &races {
+        write!(time_line, "{t:>width$}").unwrap();
+        write!(distance_line, "{d:>width$}").unwrap();
+    }
+
+    let answer_a = races.iter().map(|&(t, d)| ways_to_win(t, d)).product();
+
+    let joined = |f: fn(&(u64, u64)) -> u64| {
+        races
+            .iter()
+            .map(|r| f(r).to_string())
+            .collect::<String>()
+            .parse::<u64>()
+            .unwrap()
+    };
+    let answer_b = ways_to_win(joined(|r| r.0), joined(|r| r.1));
+
+    (
+        format!("{time_line}\n{distance_line}"),
+        Some(answer_a),
+        Some(answer_b),
+    )
+}
+
+fn ways_to_win(time: u64, distance: u64) -> u64 {
+    let (time, distance) = (u128::from(time), u128::from(distance));
+
+    // distance travelled rises until half the race, so search that half
+    let (mut lo, mut hi) = (0, time / 2 + 1);
+    while lo < hi {
+        let mid = u128::midpoint(lo, hi);
+        if mid * (time - mid) > distance {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    if lo > time / 2 {
+        0
+    } else {
+        u64::try_from(time - 2 * lo + 1).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 4);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn largest() {
+        let (input, a, b) = generate(7, 8);
+        assert_eq!(generate(7, MAX_RACES).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn example_ways() {
+        assert_eq!(ways_to_win(7, 9), 4);
+        assert_eq!(ways_to_win(30, 200), 9);
+        assert_eq!(ways_to_win(71530, 940_200), 71503);
+    }
+}
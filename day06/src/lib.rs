@@ -4,6 +4,10 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 use winnow::ascii::dec_uint;
 use winnow::ascii::line_ending;
 use winnow::ascii::multispace1;
@@ -69,13 +73,13 @@ pub fn solve_a(input: &str) -> u64 {
 fn solve_races(time: Vec<u64>, distance: Vec<u64>) -> u64 {
     let mut result = 1;
 
-    for (t, d) in time.into_iter().zip(distance.into_iter()) {
+    for (t, d) in time.into_iter().zip(distance) {
         #[allow(clippy::cast_precision_loss)]
         let t: f64 = t as f64;
         #[allow(clippy::cast_precision_loss)]
         let d: f64 = d as f64 + 0.1;
 
-        let x1 = (t + (t * t - 4.0 * d).sqrt()) / 2.0;
+        let x1 = f64::midpoint(t, (t * t - 4.0 * d).sqrt());
         let x2 = (t - (t * t - 4.0 * d).sqrt()) / 2.0;
 
         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+const CARDS: &[u8] = b"23456789TJQKA";
+const CARDS_J: &[u8] = b"J23456789TQKA";
+
+/// Generates `size` hands with bids, at least one, with the answers to both
+/// parts.
+///
+/// No hand is dealt twice, so every hand has its own rank. Answers are only
+/// given while they fit in a `u32`.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<u32>) {
+    let size = size.clamp(1, CARDS.len().pow(5));
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut dealt = HashSet::new();
+    let mut hands = vec![];
+    while hands.len() < size {
+        // drawing from a few distinct cards gives a spread of hand types
+        let distinct = rng.gen_range(1..=5);
+        let pool = CARDS
+            .choose_multiple(&mut rng, distinct)
+            .copied()
+            .collect::<Vec<_>>();
+        let hand = (0..5)
+            .map(|_| *pool.choose(&mut rng).unwrap())
+            .collect::<Vec<_>>();
+        let bid = rng.gen_range(1..=1000);
+        if dealt.insert(hand.clone()) {
+            hands.push((hand, bid));
+        }
+    }
+
+    let lines = hands
+        .iter()
+        .map(|(hand, bid)| format!("{} {bid}", String::from_utf8_lossy(hand)))
+        .collect::<Vec<_>>();
+
+    (
+        lines.join("\n"),
+        winnings(&hands, false),
+        winnings(&hands, true),
+    )
+}
+
+/// The total winnings of `hands`, with `J` as a joker if `jokers` is set.
+fn winnings(hands: &[(Vec<u8>, u32)], jokers: bool) -> Option<u32> {
+    let mut ranked = hands
+        .iter()
+        .map(|(hand, bid)| (strength(hand, jokers), bid))
+        .collect::<Vec<_>>();
+    ranked.sort_unstable();
+
+    let total = (1_u64..)
+        .zip(ranked)
+        .map(|(rank, (_, &bid))| rank * u64::from(bid))
+        .sum::<u64>();
+    u32::try_from(total).ok()
+}
+
+/// Orders hands by type, then card by card. Comparing the card counts from
+/// most to least common ranks the types, from five of a kind at `[5]` down to
+/// high card at `[1, 1, 1, 1, 1]`.
+fn strength(hand: &[u8], jokers: bool) -> (Vec<usize>, Vec<usize>) {
+    let order = if jokers { CARDS_J } else { CARDS };
+    let values = hand
+        .iter()
+        .map(|c| order.iter().position(|o| o == c).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut counts = vec![0; order.len()];
+    for &v in &values {
+        counts[v] += 1;
+    }
+    // the joker is the lowest card, so comes first in the order
+    let wild = if jokers {
+        std::mem::take(&mut counts[0])
+    } else {
+        0
+    };
+    counts.retain(|&n| n > 0);
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+
+    // jokers join the most common card, or make five of a kind alone
+    match counts.first_mut() {
+        Some(most) => *most += wild,
+        None => counts.push(wild),
+    }
+
+    (counts, values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 1000);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn example_strengths() {
+        let hands = [
+            ("32T3K", 765),
+            ("T55J5", 684),
+            ("KK677", 28),
+            ("KTJJT", 220),
+            ("QQQJA", 483),
+        ]
+        .map(|(hand, bid)| (hand.as_bytes().to_vec(), bid));
+        assert_eq!(winnings(&hands, false), Some(6440));
+        assert_eq!(winnings(&hands, true), Some(5905));
+    }
+}
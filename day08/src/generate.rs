@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+// distinct primes, so the part b answer is the instruction count times their product
+const LOOPS: [u64; 6] = [3, 5, 7, 11, 13, 17];
+
+/// Generates a network with `size` instructions, with the answers to both
+/// parts.
+///
+/// Each `**A` start walks a loop of `size * p` nodes back to its `**Z` node,
+/// for a different prime `p` per start, so the network passes `check`. There
+/// is always at least one instruction.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<u64>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let steps = (0..size)
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect::<Vec<_>>();
+
+    let loop_len = |p: u64| usize::try_from(p).unwrap() * size;
+    let total = LOOPS.iter().map(|&p| loop_len(p) + 1).sum::<usize>();
+    let mut width = 3;
+    while LETTERS.len().pow(width - 1) * (LETTERS.len() - 2) < total * 2 {
+        width += 1;
+    }
+
+    let mut used = HashSet::from(["AAA".to_owned(), "ZZZ".to_owned()]);
+
+    // every loop as [start, nodes..., end], walked in step order
+    let mut loops = vec![];
+    for (i, &p) in LOOPS.iter().enumerate() {
+        let (start, end) = if i == 0 {
+            ("AAA".to_owned(), "ZZZ".to_owned())
+        } else {
+            loop {
+                let start = unique_name(&mut rng, &mut used, width, Some(b'A'));
+                let end = format!("{}Z", &start[..start.len() - 1]);
+                if used.insert(end.clone()) {
+                    break (start, end);
+                }
+            }
+        };
+
+        let mut nodes = vec![start];
+        nodes.extend((1..loop_len(p)).map(|_| unique_name(&mut rng, &mut used, width, None)));
+        nodes.push(end);
+        loops.push(nodes);
+    }
+
+    let all_nodes = loops.iter().flatten().cloned().collect::<Vec<_>>();
+    let mut lines = vec![];
+    for nodes in &loops {
+        for (t, node) in nodes.iter().enumerate() {
+            // the end node carries on round the loop exactly as the start did
+            let next = nodes.get(t + 1).unwrap_or(&nodes[1]);
+            let decoy = all_nodes.choose(&mut rng).unwrap();
+            let (l, r) = match steps[t % size] {
+                'L' => (next, decoy),
+                _ => (decoy, next),
+            };
+            lines.push(format!("{node} = ({l}, {r})"));
+        }
+    }
+    lines.shuffle(&mut rng);
+
+    let m = u64::try_from(size).unwrap();
+    let answer_a = u32::try_from(m * LOOPS[0]).ok();
+    let answer_b = m * LOOPS.iter().product::<u64>();
+
+    (
+        format!(
+            "{}\n\n{}",
+            steps.into_iter().collect::<String>(),
+            lines.join("\n")
+        ),
+        answer_a,
+        Some(answer_b),
+    )
+}
+
+/// A name not yet in `used`, ending in `last` or else in neither `A` nor `Z`.
+fn unique_name(
+    rng: &mut StdRng,
+    used: &mut HashSet<String>,
+    width: u32,
+    last: Option<u8>,
+) -> String {
+    loop {
+        let mut name = (1..width)
+            .map(|_| char::from(*LETTERS.choose(rng).unwrap()))
+            .collect::<String>();
+        name.push(char::from(last.unwrap_or_else(|| {
+            *LETTERS[1..LETTERS.len() - 1].choose(rng).unwrap()
+        })));
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check, solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 50);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+        assert!(check(&input).is_empty());
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+        assert!(check(&input).is_empty());
+    }
+}
@@ -4,8 +4,16 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
-use std::collections::HashMap;
+mod generate;
 
+pub use generate::generate;
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::ControlFlow,
+};
+
+use itertools::Itertools;
 use num::Integer;
 use winnow::ascii::line_ending;
 use winnow::combinator::delimited;
@@ -13,7 +21,7 @@ use winnow::combinator::preceded;
 use winnow::combinator::repeat;
 use winnow::combinator::separated_pair;
 use winnow::token::one_of;
-use winnow::token::take_until1;
+use winnow::token::take_until;
 use winnow::PResult;
 use winnow::Parser;
 
@@ -35,11 +43,11 @@ fn parse_input<'a>(input: &mut &'a str) -> PResult<(Vec<Step>, Vec<Element<'a>>)
         repeat(
             1..,
             separated_pair(
-                preceded(line_ending, take_until1(" ")),
+                preceded(line_ending, take_until(1.., " ")),
                 " = ",
                 delimited(
                     '(',
-                    separated_pair(take_until1(","), ", ", take_until1(")")),
+                    separated_pair(take_until(1.., ","), ", ", take_until(1.., ")")),
                     ')',
                 ),
             ),
@@ -50,31 +58,59 @@ fn parse_input<'a>(input: &mut &'a str) -> PResult<(Vec<Step>, Vec<Element<'a>>)
 
 #[must_use]
 pub fn solve_a(input: &str) -> u32 {
+    solve_a_with(input, |_| ControlFlow::Continue(())).unwrap()
+}
+
+/// Solves part a, calling `progress` with the number of steps walked after
+/// each one. Returns `None` if `progress` breaks, which is the only way out
+/// if `ZZZ` can't be reached.
+pub fn solve_a_with(
+    input: &str,
+    mut progress: impl FnMut(usize) -> ControlFlow<()>,
+) -> Option<u32> {
     let (steps, elements) = parse_input.parse(input).unwrap();
 
     let elements: HashMap<_, _> = elements.into_iter().collect();
 
-    find_distance("AAA", &elements, &steps)
+    find_distance("AAA", &elements, &steps, &mut 0, &mut progress)
 }
 
 #[must_use]
 pub fn solve_b(input: &str) -> u64 {
+    solve_b_with(input, |_| ControlFlow::Continue(())).unwrap()
+}
+
+/// Solves part b, calling `progress` with the number of steps walked from
+/// every start so far. Returns `None` if `progress` breaks.
+pub fn solve_b_with(
+    input: &str,
+    mut progress: impl FnMut(usize) -> ControlFlow<()>,
+) -> Option<u64> {
     let (steps, elements) = parse_input.parse(input).unwrap();
     let elements: HashMap<_, _> = elements.into_iter().collect();
 
-    elements
-        .keys()
-        .filter(|e| e.ends_with('A'))
-        .map(|p| u64::from(find_distance(p, &elements, &steps)))
-        .reduce(|a, b| a.lcm(&b))
-        .unwrap()
+    let mut walked = 0;
+    let mut result = None;
+    for start in elements.keys().filter(|e| e.ends_with('A')) {
+        let distance = u64::from(find_distance(
+            start,
+            &elements,
+            &steps,
+            &mut walked,
+            &mut progress,
+        )?);
+        result = Some(result.map_or(distance, |r: u64| r.lcm(&distance)));
+    }
+    Some(result.unwrap())
 }
 
 fn find_distance<'a>(
     mut current: &'a str,
     elements: &HashMap<&'a str, (&'a str, &'a str)>,
     steps: &[Step],
-) -> u32 {
+    walked: &mut usize,
+    progress: &mut impl FnMut(usize) -> ControlFlow<()>,
+) -> Option<u32> {
     let mut steps = steps.iter().cycle();
     let mut count = 0;
 
@@ -85,9 +121,86 @@ fn find_distance<'a>(
             Step::Right => current = r,
         }
         count += 1;
+        *walked += 1;
+        if progress(*walked).is_break() {
+            return None;
+        }
     }
 
-    count
+    Some(count)
+}
+
+/// Checks the assumptions `solve_b` makes about the input.
+///
+/// The LCM in `solve_b` is only correct if every `**A` start reaches a `**Z`
+/// node after `n` steps and then keeps hitting `**Z` nodes at exactly `2n`,
+/// `3n`, ... steps. Returns a description of each start that breaks this, or
+/// where the input stops being a network.
+#[must_use]
+pub fn check(input: &str) -> Vec<String> {
+    let (steps, elements) = match parse_input.parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            // the parser stops at the line ending before a line it can't read
+            let (read, rest) = input.split_at(e.offset());
+            let line = read.matches('\n').count() + 1 + usize::from(rest.starts_with('\n'));
+            return vec![format!("invalid network at line {line}")];
+        }
+    };
+    let elements: HashMap<_, _> = elements.into_iter().collect();
+
+    elements
+        .keys()
+        .filter(|e| e.ends_with('A'))
+        .sorted()
+        .filter_map(|start| check_start(start, &elements, &steps).err())
+        .collect()
+}
+
+fn check_start<'a>(
+    start: &'a str,
+    elements: &HashMap<&'a str, (&'a str, &'a str)>,
+    steps: &[Step],
+) -> Result<(), String> {
+    // a walk this long without a Z has repeated a (node, step) state
+    let limit = elements.len() * steps.len();
+
+    let mut current = start;
+    let mut count = 0;
+    let mut since_z = 0;
+    let mut first = None;
+    let mut seen_z = HashSet::new();
+
+    loop {
+        let (l, r) = elements
+            .get(current)
+            .ok_or_else(|| format!("start {start} reaches unknown node {current}"))?;
+        current = match steps[count % steps.len()] {
+            Step::Left => l,
+            Step::Right => r,
+        };
+        count += 1;
+        since_z += 1;
+
+        if !current.ends_with('Z') {
+            if since_z > limit {
+                return Err(format!("start {start} never reaches a node ending in Z"));
+            }
+            continue;
+        }
+
+        let distance = *first.get_or_insert(count);
+        if since_z != distance {
+            return Err(format!(
+                "start {start} reaches {current} {since_z} steps after the previous Z, \
+                 expected {distance}"
+            ));
+        }
+        if !seen_z.insert((current, count % steps.len())) {
+            return Ok(());
+        }
+        since_z = 0;
+    }
 }
 
 #[cfg(test)]
@@ -120,6 +233,65 @@ XXX = (XXX, XXX)";
         assert_eq!(solve_b(INPUT_B), 6);
     }
 
+    #[test]
+    fn check_example() {
+        assert!(check(INPUT_B).is_empty());
+    }
+
+    #[test]
+    fn check_unreachable() {
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11A, 11A)
+11Z = (11Z, 11Z)";
+        assert_eq!(
+            check(input),
+            vec!["start 11A never reaches a node ending in Z"]
+        );
+    }
+
+    #[test]
+    fn check_invalid() {
+        assert_eq!(
+            check("LR\n\nAAA = (BBB, ZZZ)\nBBB = BBB"),
+            vec!["invalid network at line 4"]
+        );
+    }
+
+    #[test]
+    fn cancel_unreachable() {
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11A, 11A)
+11Z = (11Z, 11Z)";
+        let mut walked = 0;
+        let stopped = solve_b_with(input, |n| {
+            walked = n;
+            if n < 1000 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        assert_eq!(stopped, None);
+        assert_eq!(walked, 1000);
+    }
+
+    #[test]
+    fn check_uneven_cycle() {
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11Z, 11Z)";
+        assert_eq!(
+            check(input),
+            vec!["start 11A reaches 11Z 1 steps after the previous Z, expected 2"]
+        );
+    }
+
     #[test]
     fn solution_a() {
         assert_eq!(solve_a(include_str!("input.txt")), 17873);
@@ -129,4 +301,9 @@ XXX = (XXX, XXX)";
     fn solution_b() {
         assert_eq!(solve_b(include_str!("input.txt")), 15_746_133_679_061);
     }
+
+    #[test]
+    fn solution_check() {
+        assert!(check(include_str!("input.txt")).is_empty());
+    }
 }
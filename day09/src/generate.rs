@@ -0,0 +1,77 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const VALUES: i64 = 21;
+
+/// Generates `size` histories, at least one, with the answers to both parts.
+///
+/// Each history is a polynomial `p(x) = sum(d[k] * C(x, k))` sampled at
+/// `x = 0..21`, where `d` are its initial differences. The extrapolated values
+/// are then `p(21)` and `p(-1) = sum(d[k] * (-1)^k)`.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<i32>, Option<i32>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut lines = vec![];
+    let mut sum_a = 0;
+    let mut sum_b = 0;
+
+    for _ in 0..size {
+        let differences = (0..=rng.gen_range(0..=6))
+            .map(|_| rng.gen_range(-10..=10))
+            .collect::<Vec<i64>>();
+        let p = |x: i64| {
+            differences
+                .iter()
+                .enumerate()
+                .map(|(k, d)| d * choose(x, k))
+                .sum::<i64>()
+        };
+
+        lines.push(
+            (0..VALUES)
+                .map(|x| p(x).to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        sum_a += p(VALUES);
+        sum_b += p(-1);
+    }
+
+    (
+        lines.join("\n"),
+        i32::try_from(sum_a).ok(),
+        i32::try_from(sum_b).ok(),
+    )
+}
+
+/// The generalised binomial coefficient, defined for negative `n` too.
+fn choose(n: i64, k: usize) -> i64 {
+    let mut result = 1;
+    for i in 0..i64::try_from(k).unwrap() {
+        // exact: the product of i + 1 consecutive integers divides by (i + 1)!
+        result = result * (n - i) / (i + 1);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 200);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+}
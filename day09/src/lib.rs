@@ -4,6 +4,10 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 use itertools::Itertools;
 use winnow::{
     ascii::{dec_int, line_ending},
@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+const JUNK: &[u8] = b"|-LJ7F...";
+
+/// Generates a `size` by `size` grid holding one closed loop, with the
+/// answers to both parts.
+///
+/// The loop is traced round the edge of a random polyomino with no holes,
+/// drawn on the corners of its cells. If the polyomino has area `A` and the
+/// loop is `B` tiles long then, by Pick's theorem, `A - B / 2 + 1` tiles are
+/// enclosed. A loop needs a grid of at least 3 by 3, so smaller sizes give
+/// one that size.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<u32>) {
+    let size = size.max(3);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let cells = size - 1;
+
+    let mut shape = HashSet::from([(cells / 2, cells / 2)]);
+    let mut frontier = neighbours(cells / 2, cells / 2, cells);
+    for _ in 0..cells * cells / 2 {
+        if frontier.is_empty() {
+            break;
+        }
+        let (x, y) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if !shape.contains(&(x, y)) && keeps_loop_simple(&shape, x, y) {
+            shape.insert((x, y));
+            frontier.extend(neighbours(x, y, cells));
+        }
+    }
+
+    let mut grid = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| char::from(*JUNK.choose(&mut rng).unwrap()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // a corner is on the loop if the four cells around it are mixed
+    let filled = |x: usize, y: usize, dx: usize, dy: usize| {
+        x + dx > 0 && y + dy > 0 && shape.contains(&(x + dx - 1, y + dy - 1))
+    };
+    let mut path = vec![];
+    for (y, row) in grid.iter_mut().enumerate() {
+        for (x, tile) in row.iter_mut().enumerate() {
+            let (nw, ne, sw, se) = (
+                filled(x, y, 0, 0),
+                filled(x, y, 1, 0),
+                filled(x, y, 0, 1),
+                filled(x, y, 1, 1),
+            );
+            let (n, s, w, e) = (nw != ne, sw != se, nw != sw, ne != se);
+            let pipe = match (n, s, w, e) {
+                (true, true, false, false) => '|',
+                (false, false, true, true) => '-',
+                (true, false, false, true) => 'L',
+                (true, false, true, false) => 'J',
+                (false, true, true, false) => '7',
+                (false, true, false, true) => 'F',
+                _ => continue,
+            };
+            *tile = pipe;
+            path.push((x, y));
+        }
+    }
+
+    let &(sx, sy) = path.choose(&mut rng).unwrap();
+    grid[sy][sx] = 'S';
+    // junk next to the start mustn't look like it joins it
+    for (x, y) in [
+        (sx.wrapping_sub(1), sy),
+        (sx + 1, sy),
+        (sx, sy.wrapping_sub(1)),
+        (sx, sy + 1),
+    ] {
+        if !path.contains(&(x, y)) {
+            if let Some(tile) = grid.get_mut(y).and_then(|r| r.get_mut(x)) {
+                *tile = '.';
+            }
+        }
+    }
+
+    let boundary = u32::try_from(path.len()).unwrap();
+    let area = u32::try_from(shape.len()).unwrap();
+
+    (
+        grid.into_iter()
+            .map(|r| r.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(boundary / 2),
+        Some(area + 1 - boundary / 2),
+    )
+}
+
+fn neighbours(x: usize, y: usize, cells: usize) -> Vec<(usize, usize)> {
+    [
+        (x.wrapping_sub(1), y),
+        (x + 1, y),
+        (x, y.wrapping_sub(1)),
+        (x, y + 1),
+    ]
+    .into_iter()
+    .filter(|&(x, y)| x < cells && y < cells)
+    .collect()
+}
+
+/// Adding a cell keeps the outline a single loop if the filled cells around
+/// it form one unbroken arc, so it neither closes a hole nor touches the
+/// shape only at a corner.
+fn keeps_loop_simple(shape: &HashSet<(usize, usize)>, x: usize, y: usize) -> bool {
+    let ring = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ]
+    .map(|(dx, dy)| {
+        x.checked_add_signed(dx)
+            .zip(y.checked_add_signed(dy))
+            .is_some_and(|c| shape.contains(&c))
+    });
+
+    let arcs = (0..ring.len())
+        .filter(|&i| ring[i] && !ring[(i + ring.len() - 1) % ring.len()])
+        .count();
+    arcs == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check, solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 40);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+        assert!(check(&input).is_empty());
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(1, 0);
+        assert_eq!(generate(1, 3).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+        assert!(check(&input).is_empty());
+    }
+}
@@ -4,6 +4,10 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 use std::collections::HashSet;
 
 use itertools::Itertools;
@@ -136,6 +140,82 @@ fn find(grid: &[Vec<char>], target: char) -> Option<(usize, usize)> {
     })
 }
 
+/// Checks the assumptions `loop_coords` makes about the input.
+///
+/// The grid must hold only pipe, ground and start tiles, with exactly one
+/// `S`, and that `S` must join exactly two of its neighbouring pipes.
+/// Returns a description of each failure.
+#[must_use]
+pub fn check(input: &str) -> Vec<String> {
+    let grid = parse_input(input);
+    let unknown = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, l)| l.iter().enumerate().map(move |(x, &c)| (y, x, c)))
+        .filter(|&(_, _, c)| !"|-LJ7F.S".contains(c))
+        .map(|(y, x, c)| format!("unknown tile '{c}' at line {}, column {}", y + 1, x + 1))
+        .collect_vec();
+    if !unknown.is_empty() {
+        return unknown;
+    }
+
+    let starts = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, l)| {
+            l.iter()
+                .enumerate()
+                .filter_map(move |(x, &c)| (c == 'S').then_some((y, x)))
+        })
+        .collect_vec();
+
+    match starts[..] {
+        [] => vec!["no start 'S' found".to_owned()],
+        [(y, x)] => {
+            let connections = [
+                (y > 0)
+                    .then(|| grid.get(y - 1).and_then(|l| l.get(x)))
+                    .flatten()
+                    .is_some_and(|c| matches!(c, '7' | 'F' | '|')),
+                grid.get(y + 1)
+                    .and_then(|l| l.get(x))
+                    .is_some_and(|c| matches!(c, 'L' | 'J' | '|')),
+                (x > 0)
+                    .then(|| grid[y].get(x - 1))
+                    .flatten()
+                    .is_some_and(|c| matches!(c, 'F' | 'L' | '-')),
+                grid[y]
+                    .get(x + 1)
+                    .is_some_and(|c| matches!(c, '7' | 'J' | '-')),
+            ]
+            .into_iter()
+            .filter(|&c| c)
+            .count();
+
+            if connections == 2 {
+                vec![]
+            } else {
+                vec![format!(
+                    "start 'S' at line {}, column {} joins {connections} pipes, expected 2",
+                    y + 1,
+                    x + 1
+                )]
+            }
+        }
+        _ => starts
+            .iter()
+            .map(|(y, x)| {
+                format!(
+                    "start 'S' at line {}, column {} is one of {}, expected exactly one",
+                    y + 1,
+                    x + 1,
+                    starts.len()
+                )
+            })
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,6 +244,42 @@ LJ.LJ";
         assert_eq!(solve_b(INPUT_B), 4);
     }
 
+    #[test]
+    fn check_examples() {
+        assert!(check(INPUT_A).is_empty());
+        assert!(check(INPUT_B).is_empty());
+    }
+
+    #[test]
+    fn check_three_pipes() {
+        let input = ".|.
+-S-
+...";
+        assert_eq!(
+            check(input),
+            vec!["start 'S' at line 2, column 2 joins 3 pipes, expected 2"]
+        );
+    }
+
+    #[test]
+    fn check_two_starts() {
+        assert_eq!(
+            check("S-S"),
+            vec![
+                "start 'S' at line 1, column 1 is one of 2, expected exactly one",
+                "start 'S' at line 1, column 3 is one of 2, expected exactly one"
+            ]
+        );
+    }
+
+    #[test]
+    fn check_unknown_tile() {
+        assert_eq!(
+            check("S-7\n|x|\nL-J"),
+            vec!["unknown tile 'x' at line 2, column 2"]
+        );
+    }
+
     #[test]
     fn solution_a() {
         assert_eq!(solve_a(include_str!("input.txt")), 6640);
@@ -173,4 +289,9 @@ LJ.LJ";
     fn solution_b() {
         assert_eq!(solve_b(include_str!("input.txt")), 411);
     }
+
+    #[test]
+    fn solution_check() {
+        assert!(check(include_str!("input.txt")).is_empty());
+    }
 }
@@ -0,0 +1,101 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Generates a `size` by `size` image, with the answers to both parts.
+///
+/// There are always at least two galaxies, in opposite corners if none are
+/// placed at random, so the image is at least 2 by 2.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u64>, Option<u64>) {
+    let size = size.max(2);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // leave a few whole rows and columns empty to be expanded
+    let empty_rows = (0..size).map(|_| rng.gen_bool(0.05)).collect::<Vec<_>>();
+    let empty_cols = (0..size).map(|_| rng.gen_bool(0.05)).collect::<Vec<_>>();
+
+    let mut grid = (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| !empty_rows[y] && !empty_cols[x] && rng.gen_bool(0.02))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    if grid.iter().flatten().filter(|&&g| g).count() < 2 {
+        grid[0][0] = true;
+        grid[size - 1][size - 1] = true;
+    }
+
+    let galaxies = (0..size)
+        .flat_map(|y| (0..size).map(move |x| (x, y)))
+        .filter(|&(x, y)| grid[y][x])
+        .collect::<Vec<_>>();
+    let lines = grid
+        .iter()
+        .map(|l| {
+            l.iter()
+                .map(|&g| if g { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    (
+        lines.join("\n"),
+        Some(expanded_distances(&galaxies, size, 2)),
+        Some(expanded_distances(&galaxies, size, 1_000_000)),
+    )
+}
+
+/// Sums the distances between every pair of galaxies once each empty row and
+/// column is `factor` times as wide, a coordinate axis at a time.
+fn expanded_distances(galaxies: &[(usize, usize)], size: usize, factor: u64) -> u64 {
+    let axis = |coords: Vec<usize>| {
+        let mut occupied = vec![false; size];
+        for &c in &coords {
+            occupied[c] = true;
+        }
+
+        let mut expanded = vec![0; size];
+        let mut position = 0;
+        for (c, &o) in occupied.iter().enumerate() {
+            expanded[c] = position;
+            position += if o { 1 } else { factor };
+        }
+
+        let mut sorted = coords.iter().map(|&c| expanded[c]).collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        // each coordinate is the larger of a pair with every one before it
+        let mut before = 0;
+        let mut total = 0;
+        for (i, &c) in (0_u64..).zip(&sorted) {
+            total += c * i - before;
+            before += c;
+        }
+        total
+    };
+
+    axis(galaxies.iter().map(|&(x, _)| x).collect())
+        + axis(galaxies.iter().map(|&(_, y)| y).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 60);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(7, 0);
+        assert_eq!(generate(7, 2).0, input);
+        assert_eq!(input, "#.\n.#");
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+}
@@ -4,6 +4,10 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 use std::collections::HashSet;
 
 use itertools::Itertools;
@@ -0,0 +1,71 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const MAX_LEN: usize = 20;
+
+/// Generates `size` spring records, at least one. Counting arrangements is the puzzle, so no
+/// answers are given, but each record is built from a real arrangement and so
+/// has at least one.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u64>, Option<u64>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let lines = (0..size)
+        .map(|_| {
+            let mut groups: Vec<usize> = vec![];
+            // each group after the first also needs a '.' before it
+            while groups.len() < 6 {
+                let g = rng.gen_range(1..=5);
+                let needed = groups.iter().sum::<usize>() + groups.len() + g;
+                if needed > MAX_LEN {
+                    break;
+                }
+                groups.push(g);
+            }
+
+            let mut springs = String::new();
+            for (i, &g) in groups.iter().enumerate() {
+                let min_gap = usize::from(i > 0);
+                let needed = min_gap + groups[i..].iter().sum::<usize>() + groups.len() - i - 1;
+                let slack = (MAX_LEN - springs.len() - needed).min(3);
+                springs.push_str(&".".repeat(rng.gen_range(min_gap..=min_gap + slack)));
+                springs.push_str(&"#".repeat(g));
+            }
+            springs.push_str(&".".repeat(rng.gen_range(0..=MAX_LEN - springs.len())));
+
+            let record = springs
+                .chars()
+                .map(|c| if rng.gen_bool(0.5) { '?' } else { c })
+                .collect::<String>();
+            let groups = groups
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{record} {groups}")
+        })
+        .collect::<Vec<_>>();
+
+    (lines.join("\n"), None, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve_a;
+
+    #[test]
+    fn generated_records_are_solvable() {
+        let (input, _, _) = generate(1, 50);
+        for line in input.lines() {
+            assert!(solve_a(line) > 0, "{line}");
+        }
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, _, _) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert!(solve_a(&input) > 0);
+    }
+}
@@ -4,12 +4,18 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
+use std::ops::ControlFlow;
+
 use itertools::Itertools;
 use winnow::{
     ascii::{dec_uint, line_ending},
     combinator::{alt, eof, repeat, separated, separated_pair, terminated},
     error::{ContextError, ErrMode},
-    token::{one_of, take_until1},
+    token::{one_of, take_until},
     PResult, Parser,
 };
 
@@ -18,7 +24,7 @@ fn parse_lines<'a>(input: &mut &'a str) -> PResult<Vec<(&'a str, Vec<u32>)>> {
 }
 fn parse_line<'a>(input: &mut &'a str) -> PResult<(&'a str, Vec<u32>)> {
     separated_pair(
-        take_until1(" "),
+        take_until(1.., " "),
         " ",
         separated(1.., dec_uint::<_, u32, _>, ","),
     )
@@ -32,17 +38,27 @@ fn group_parser<'a>(len: usize) -> impl Parser<&'a str, (), ErrMode<ContextError
     )
 }
 
-fn solve(lines: Vec<(String, Vec<u32>)>) -> u64 {
+fn solve(
+    lines: Vec<(String, Vec<u32>)>,
+    mut progress: impl FnMut(usize) -> ControlFlow<()>,
+) -> Option<u64> {
     let mut result = 0;
+    let mut step = 0;
 
     for (record, groups) in lines {
         let mut c = vec![(1, &record[..], &groups[..])];
 
         loop {
-            c = c
-                .into_iter()
-                .flat_map(|(n, r, g)| possible_next(n, r, g))
-                .collect();
+            // a round can hold a great many states, so report each one
+            let mut next = vec![];
+            for (n, r, g) in c {
+                next.extend(possible_next(n, r, g));
+                step += 1;
+                if progress(step).is_break() {
+                    return None;
+                }
+            }
+            c = next;
 
             result += c
                 .iter()
@@ -67,7 +83,7 @@ fn solve(lines: Vec<(String, Vec<u32>)>) -> u64 {
             }
         }
     }
-    result.try_into().unwrap()
+    Some(result.try_into().unwrap())
 }
 
 fn possible_next<'a>(
@@ -126,13 +142,27 @@ fn possible_next<'a>(
 
 #[must_use]
 pub fn solve_a(input: &str) -> u64 {
+    solve_a_with(input, |_| ControlFlow::Continue(())).unwrap()
+}
+
+/// Solves part a, calling `progress` after expanding each state of the
+/// frontier, with the number of states expanded so far. Returns `None` if
+/// `progress` breaks.
+pub fn solve_a_with(input: &str, progress: impl FnMut(usize) -> ControlFlow<()>) -> Option<u64> {
     let lines = parse_lines.parse(input).unwrap();
     let lines = lines.into_iter().map(|(r, g)| (r.to_owned(), g)).collect();
-    solve(lines)
+    solve(lines, progress)
 }
 
 #[must_use]
 pub fn solve_b(input: &str) -> u64 {
+    solve_b_with(input, |_| ControlFlow::Continue(())).unwrap()
+}
+
+/// Solves part b, calling `progress` after expanding each state of the
+/// frontier, with the number of states expanded so far. Returns `None` if
+/// `progress` breaks.
+pub fn solve_b_with(input: &str, progress: impl FnMut(usize) -> ControlFlow<()>) -> Option<u64> {
     let lines = parse_lines.parse(input).unwrap();
     let lines = lines
         .into_iter()
@@ -157,7 +187,7 @@ pub fn solve_b(input: &str) -> u64 {
         })
         .collect_vec();
 
-    solve(lines)
+    solve(lines, progress)
 }
 
 #[cfg(test)]
@@ -185,6 +215,46 @@ mod tests {
         assert_eq!(solve_b(INPUT), 525_152);
     }
 
+    #[test]
+    fn cancel_b() {
+        let mut steps = 0;
+        let stopped = solve_b_with(INPUT, |n| {
+            steps = n;
+            if n < 50 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        assert_eq!(stopped, None);
+        assert_eq!(steps, 50);
+    }
+
+    #[test]
+    fn cancel_within_round() {
+        // at most a round per spring, each expanding several states
+        let input = format!("{} 1,1,1,1", "?".repeat(40));
+        let mut steps = 0;
+        assert!(solve_a_with(&input, |n| {
+            steps = n;
+            ControlFlow::Continue(())
+        })
+        .is_some());
+        assert!(steps > 4 * 40, "{steps}");
+
+        let stop = steps / 2;
+        let stopped = solve_a_with(&input, |n| {
+            steps = n;
+            if n < stop {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        assert_eq!(stopped, None);
+        assert_eq!(steps, stop);
+    }
+
     #[test]
     fn solution_a() {
         assert_eq!(solve_a(include_str!("input.txt")), 6935);
@@ -0,0 +1,106 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{mirror_rows, smudge_rows, transpose};
+
+/// Generates `size` patterns, at least one, with the answers to both parts.
+///
+/// Each pattern is built to reflect exactly across one column, and across
+/// one row apart from a single smudged cell, then turned on its side half
+/// the time. Patterns with any other reflection are thrown away, so every
+/// pattern passes `check`.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<usize>, Option<usize>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut patterns = vec![];
+    let mut sum_a = 0;
+    let mut sum_b = 0;
+
+    while patterns.len() < size {
+        let Some((rows, a, b)) = candidate(&mut rng) else {
+            continue;
+        };
+        patterns.push(rows.join("\n"));
+        sum_a += a;
+        sum_b += b;
+    }
+
+    (patterns.join("\n\n"), Some(sum_a), Some(sum_b))
+}
+
+fn candidate(rng: &mut StdRng) -> Option<(Vec<String>, usize, usize)> {
+    let width = rng.gen_range(5..=17);
+    let height = rng.gen_range(5..=17);
+
+    // reflect after column `col` and after row `row`, both short of the far
+    // edge so some column sits outside the column reflection
+    let col: usize = rng.gen_range(0..width - 1);
+    let row: usize = rng.gen_range(0..height - 1);
+    if col + 1 == width - col - 1 {
+        return None;
+    }
+
+    let mut grid = (0..height)
+        .map(|_| (0..width).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let col_reach = (col + 1).min(width - col - 1);
+    for line in &mut grid {
+        for i in 0..col_reach {
+            line[col + 1 + i] = line[col - i];
+        }
+    }
+    let row_reach = (row + 1).min(height - row - 1);
+    for i in 0..row_reach {
+        let mirrored = grid[row - i].clone();
+        grid[row + 1 + i] = mirrored;
+    }
+
+    // smudge a cell the row reflection covers but the column one doesn't
+    let outside = if col + 1 < width - col - 1 {
+        rng.gen_range(2 * col + 2..width)
+    } else {
+        rng.gen_range(0..width - 2 * (width - col - 1))
+    };
+    let inside = rng.gen_range(row + 1 - row_reach..=row + row_reach);
+    grid[inside][outside] = !grid[inside][outside];
+
+    let rows = grid
+        .into_iter()
+        .map(|l| l.into_iter().map(|c| if c { '#' } else { '.' }).collect())
+        .collect::<Vec<String>>();
+    let (rows, a, b) = if rng.gen_bool(0.5) {
+        (transpose(&rows), (col + 1) * 100, row + 1)
+    } else {
+        (rows, col + 1, (row + 1) * 100)
+    };
+
+    let t = transpose(&rows);
+    let unique = mirror_rows(&rows).count() + mirror_rows(&t).count() == 1
+        && smudge_rows(&rows).count() + smudge_rows(&t).count() == 1;
+    unique.then_some((rows, a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check, solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 100);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+        assert!(check(&input).is_empty());
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+        assert!(check(&input).is_empty());
+    }
+}
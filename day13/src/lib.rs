@@ -4,6 +4,10 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 use winnow::{ascii::line_ending, combinator::separated, token::take_while, PResult, Parser};
 
 fn parse_map(input: &mut &str) -> PResult<Vec<String>> {
@@ -29,20 +33,8 @@ pub fn solve_a(input: &str) -> usize {
                 return (value + 1) * 100;
             }
 
-            // transpose
-            let mut transpose: Vec<String> = vec![];
-            for s in &m {
-                for (x, c) in s.chars().enumerate() {
-                    if let Some(s) = transpose.get_mut(x) {
-                        s.push(c);
-                    } else {
-                        transpose.push(c.to_string());
-                    }
-                }
-            }
-
             // try again
-            if let Some(value) = find_mirror_rows(&transpose) {
+            if let Some(value) = find_mirror_rows(&transpose(&m)) {
                 return value + 1;
             }
             panic!("no mirror found for {m:?}")
@@ -50,8 +42,26 @@ pub fn solve_a(input: &str) -> usize {
         .sum()
 }
 
+fn transpose(rows: &[String]) -> Vec<String> {
+    let mut transpose: Vec<String> = vec![];
+    for s in rows {
+        for (x, c) in s.chars().enumerate() {
+            if let Some(s) = transpose.get_mut(x) {
+                s.push(c);
+            } else {
+                transpose.push(c.to_string());
+            }
+        }
+    }
+    transpose
+}
+
 fn find_mirror_rows(rows: &[String]) -> Option<usize> {
-    (0..(rows.len() - 1)).find(|&i| {
+    mirror_rows(rows).next()
+}
+
+fn mirror_rows(rows: &[String]) -> impl Iterator<Item = usize> + '_ {
+    (0..(rows.len() - 1)).filter(|&i| {
         (0..=i).all(|j| {
             let mirror_row = i * 2 - j + 1;
             if let Some(right) = rows.get(mirror_row) {
@@ -73,20 +83,8 @@ pub fn solve_b(input: &str) -> usize {
                 return (value + 1) * 100;
             }
 
-            // transpose
-            let mut transpose: Vec<String> = vec![];
-            for s in &m {
-                for (x, c) in s.chars().enumerate() {
-                    if let Some(s) = transpose.get_mut(x) {
-                        s.push(c);
-                    } else {
-                        transpose.push(c.to_string());
-                    }
-                }
-            }
-
             // try again
-            if let Some(value) = find_smudge_rows(&transpose) {
+            if let Some(value) = find_smudge_rows(&transpose(&m)) {
                 return value + 1;
             }
             panic!("no mirror found for {m:?}")
@@ -95,7 +93,11 @@ pub fn solve_b(input: &str) -> usize {
 }
 
 fn find_smudge_rows(rows: &[String]) -> Option<usize> {
-    (0..(rows.len() - 1)).find(|&i| {
+    smudge_rows(rows).next()
+}
+
+fn smudge_rows(rows: &[String]) -> impl Iterator<Item = usize> + '_ {
+    (0..(rows.len() - 1)).filter(|&i| {
         let mut single_error = false;
         (0..=i).all(|j| {
             let mirror_row = i * 2 - j + 1;
@@ -131,6 +133,45 @@ fn single_difference(right: &str, left: &str) -> bool {
     single_error
 }
 
+/// Checks the assumptions `solve_a` and `solve_b` make about the input.
+///
+/// Each pattern must have exactly one line of reflection, and exactly one
+/// line of reflection once its smudge is fixed. Returns a description of
+/// each pattern that breaks this.
+#[must_use]
+pub fn check(input: &str) -> Vec<String> {
+    let maps = match parse_maps.parse(input) {
+        Ok(maps) => maps,
+        Err(e) => {
+            let line = input[..e.offset()].matches('\n').count() + 1;
+            return vec![format!("invalid pattern at line {line}")];
+        }
+    };
+
+    let mut warnings = vec![];
+    let mut line = 1;
+    for (i, m) in maps.iter().enumerate() {
+        let t = transpose(m);
+        let mirrors = mirror_rows(m).count() + mirror_rows(&t).count();
+        let smudges = smudge_rows(m).count() + smudge_rows(&t).count();
+
+        if mirrors != 1 {
+            warnings.push(format!(
+                "pattern {} at line {line} has {mirrors} reflections, expected 1",
+                i + 1
+            ));
+        }
+        if smudges != 1 {
+            warnings.push(format!(
+                "pattern {} at line {line} has {smudges} smudged reflections, expected 1",
+                i + 1
+            ));
+        }
+        line += m.len() + 1;
+    }
+    warnings
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +202,34 @@ mod tests {
         assert_eq!(solve_b(INPUT), 400);
     }
 
+    #[test]
+    fn check_example() {
+        assert!(check(INPUT).is_empty());
+    }
+
+    #[test]
+    fn check_ambiguous() {
+        let input = "#..#
+#..#
+
+##
+.#";
+        assert_eq!(
+            check(input),
+            vec![
+                "pattern 1 at line 1 has 2 reflections, expected 1",
+                "pattern 1 at line 1 has 0 smudged reflections, expected 1",
+                "pattern 2 at line 4 has 0 reflections, expected 1",
+                "pattern 2 at line 4 has 2 smudged reflections, expected 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_invalid() {
+        assert_eq!(check("#.\n.#\n\n# ."), vec!["invalid pattern at line 4"]);
+    }
+
     #[test]
     fn solution_a() {
         assert_eq!(solve_a(include_str!("input.txt")), 37_561);
@@ -170,4 +239,9 @@ mod tests {
     fn solution_b() {
         assert_eq!(solve_b(include_str!("input.txt")), 31_108);
     }
+
+    #[test]
+    fn solution_check() {
+        assert!(check(include_str!("input.txt")).is_empty());
+    }
 }
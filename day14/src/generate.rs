@@ -0,0 +1,66 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Generates a `size` by `size` platform, at least 1 by 1, with the answer
+/// to part a.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<usize>, Option<usize>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let grid = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| match rng.gen_range(0..20) {
+                    0..=3 => 'O',
+                    4..=6 => '#',
+                    _ => '.',
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // each rock rolls north to just below the last rock or cube in its column
+    let mut load = 0;
+    for x in 0..size {
+        let mut free = 0;
+        for (y, line) in grid.iter().enumerate() {
+            match line[x] {
+                'O' => {
+                    load += size - free;
+                    free += 1;
+                }
+                '#' => free = y + 1,
+                _ => {}
+            }
+        }
+    }
+
+    (
+        grid.into_iter()
+            .map(|l| l.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(load),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check, solve_a};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, _) = generate(1, 50);
+        assert_eq!(Some(solve_a(&input)), a);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, _) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert!(check(&input).is_empty());
+    }
+}
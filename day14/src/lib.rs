@@ -4,10 +4,13 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 use std::{
     collections::{HashMap, HashSet, VecDeque},
-    ops::RangeBounds,
-    usize,
+    ops::{ControlFlow, RangeBounds},
 };
 
 use itertools::Itertools;
@@ -187,13 +190,12 @@ fn tilt_east(
         .collect()
 }
 
-fn to_columns(
-    round: &[(usize, usize)],
-    cube: &[(usize, usize)],
-) -> (
+type Lines = (
     HashMap<usize, HashSet<(usize, usize)>>,
     HashMap<usize, Vec<(usize, usize)>>,
-) {
+);
+
+fn to_columns(round: &[(usize, usize)], cube: &[(usize, usize)]) -> Lines {
     let round: HashMap<usize, HashSet<_>> = round
         .iter()
         .copied()
@@ -214,13 +216,7 @@ fn to_columns(
     (round, cube)
 }
 
-fn to_rows(
-    round: &[(usize, usize)],
-    cube: &[(usize, usize)],
-) -> (
-    HashMap<usize, HashSet<(usize, usize)>>,
-    HashMap<usize, Vec<(usize, usize)>>,
-) {
+fn to_rows(round: &[(usize, usize)], cube: &[(usize, usize)]) -> Lines {
     let round: HashMap<usize, HashSet<_>> = round
         .iter()
         .copied()
@@ -278,8 +274,20 @@ fn roll_down(
     }
 }
 
+const SPINS: usize = 300;
+const WINDOW: usize = 30;
+
 #[must_use]
 pub fn solve_b(input: &str) -> usize {
+    solve_b_with(input, |_| ControlFlow::Continue(())).unwrap()
+}
+
+/// Solves part b, calling `progress` with the number of spins done after
+/// each one. Returns `None` if `progress` breaks.
+pub fn solve_b_with(
+    input: &str,
+    mut progress: impl FnMut(usize) -> ControlFlow<()>,
+) -> Option<usize> {
     let y_len = input.lines().count();
     let x_len = input.lines().next().unwrap().len();
 
@@ -288,55 +296,147 @@ pub fn solve_b(input: &str) -> usize {
 
     let mut score_history = VecDeque::new();
 
-    for _ in 0..300 {
-        let r_len = round.len();
-
-        round = tilt_north(&round, &cube, x_len);
-        assert_eq!(round.len(), r_len);
-
-        let r_len = round.len();
-        round = tilt_west(&round, &cube, y_len);
-        assert_eq!(round.len(), r_len);
-
-        let r_len = round.len();
-        round = tilt_south(&round, &cube, x_len, y_len);
-        assert_eq!(round.len(), r_len);
-
-        let r_len = round.len();
-        round = tilt_east(&round, &cube, y_len, x_len);
-        assert_eq!(round.len(), r_len);
+    for i in 0..SPINS {
+        round = spin(&round, &cube, x_len, y_len);
 
         let score = score(&round, y_len);
 
         score_history.push_back(score);
-        while score_history.len() > 30 {
+        while score_history.len() > WINDOW {
             score_history.pop_front();
         }
+
+        if progress(i + 1).is_break() {
+            return None;
+        }
     }
 
-    // detect cycles < 30
+    let cycle = find_cycle(&score_history).unwrap();
+
+    let end = 1_000_000_000 % cycle;
+
+    let offset = (SPINS - WINDOW) % cycle;
+
+    if end > offset {
+        Some(score_history[end - offset - 1])
+    } else {
+        Some(score_history[cycle + end - offset - 1])
+    }
+}
+
+fn spin(
+    round: &[(usize, usize)],
+    cube: &[(usize, usize)],
+    x_len: usize,
+    y_len: usize,
+) -> Vec<(usize, usize)> {
+    let r_len = round.len();
+
+    let round = tilt_north(round, cube, x_len);
+    assert_eq!(round.len(), r_len);
+
+    let round = tilt_west(&round, cube, y_len);
+    assert_eq!(round.len(), r_len);
+
+    let round = tilt_south(&round, cube, x_len, y_len);
+    assert_eq!(round.len(), r_len);
+
+    let round = tilt_east(&round, cube, y_len, x_len);
+    assert_eq!(round.len(), r_len);
+
+    round
+}
 
-    let first: (usize, usize, usize, usize) = score_history.iter().copied().next_tuple().unwrap();
+// detect cycles < 30
+fn find_cycle(score_history: &VecDeque<usize>) -> Option<usize> {
+    let first: (usize, usize, usize, usize) = score_history.iter().copied().next_tuple()?;
 
-    let (mut cycle, _) = score_history
+    let (cycle, _) = score_history
         .iter()
         .skip(4)
         .copied()
         .tuple_windows()
-        .find_position(|x: &(_, _, _, _)| x == &first)
-        .unwrap();
+        .find_position(|x: &(_, _, _, _)| x == &first)?;
 
-    cycle += 4;
+    Some(cycle + 4)
+}
 
-    let end = 1_000_000_000 % cycle;
+/// Checks the assumptions `solve_b` makes about the input.
+///
+/// `solve_b` spins the platform 300 times and looks for the period in the
+/// last 30 scores, so the positions must have settled into a cycle short
+/// enough to show up in that window, and the scores alone must identify it.
+/// A platform that isn't a rectangle of `O`, `#` and `.`, or that has no
+/// rows or no columns, is reported before spinning. Returns a description of each failure.
+#[must_use]
+pub fn check(input: &str) -> Vec<String> {
+    let y_len = input.lines().count();
+    let Some(x_len) = input.lines().next().map(str::len) else {
+        return vec!["no platform found".to_owned()];
+    };
+    if x_len == 0 {
+        return vec!["platform has no columns".to_owned()];
+    }
+    for (y, line) in input.lines().enumerate() {
+        if let Some((x, c)) = line.chars().enumerate().find(|(_, c)| !"O#.".contains(*c)) {
+            return vec![format!(
+                "unknown tile '{c}' at line {}, column {}",
+                y + 1,
+                x + 1
+            )];
+        }
+        if line.len() != x_len {
+            return vec![format!(
+                "line {} is {} tiles wide, expected {x_len}",
+                y + 1,
+                line.len()
+            )];
+        }
+    }
+
+    let mut round = grid_coords(input.lines(), 'O');
+    let cube = grid_coords(input.lines(), '#');
 
-    let offset = (300 - 30) % cycle;
+    let mut seen = HashMap::new();
+    let mut score_history = VecDeque::new();
+    let mut cycle = None;
 
-    if end > offset {
-        score_history[end - offset - 1]
-    } else {
-        score_history[cycle + end - offset - 1]
+    for i in 0..SPINS {
+        round = spin(&round, &cube, x_len, y_len);
+        round.sort_unstable();
+
+        if cycle.is_none() {
+            if let Some(start) = seen.insert(round.clone(), i) {
+                cycle = Some((start, i - start));
+            }
+        }
+
+        score_history.push_back(score(&round, y_len));
+        while score_history.len() > WINDOW {
+            score_history.pop_front();
+        }
+    }
+
+    let Some((start, length)) = cycle else {
+        return vec![format!("no repeated position within {SPINS} spins")];
+    };
+
+    let mut warnings = vec![];
+    if start > SPINS - WINDOW {
+        warnings.push(format!(
+            "cycle starts after spin {start}, inside the last {WINDOW} spins"
+        ));
+    }
+    match find_cycle(&score_history) {
+        None => warnings.push(format!(
+            "cycle length {length} is too long to find in the last {WINDOW} scores"
+        )),
+        Some(found) if found % length != 0 => warnings.push(format!(
+            "scores repeat every {found} spins but positions repeat every {length}"
+        )),
+        Some(_) => {}
     }
+    warnings
 }
 
 fn score(round: &[(usize, usize)], y_len: usize) -> usize {
@@ -366,6 +466,40 @@ O.#..O.#.#
         assert_eq!(solve_b(INPUT), 64);
     }
 
+    #[test]
+    fn cancel_b() {
+        let mut spins = 0;
+        let stopped = solve_b_with(INPUT, |n| {
+            spins = n;
+            if n == 10 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(stopped, None);
+        assert_eq!(spins, 10);
+    }
+
+    #[test]
+    fn check_example() {
+        assert!(check(INPUT).is_empty());
+    }
+
+    #[test]
+    fn check_invalid() {
+        assert_eq!(check(""), vec!["no platform found"]);
+        assert_eq!(check("\n"), vec!["platform has no columns"]);
+        assert_eq!(
+            check("O.#\n.x."),
+            vec!["unknown tile 'x' at line 2, column 2"]
+        );
+        assert_eq!(
+            check("O.#\n..\n#.O"),
+            vec!["line 2 is 2 tiles wide, expected 3"]
+        );
+    }
+
     #[test]
     fn solution_a() {
         assert_eq!(solve_a(include_str!("input.txt")), 106_997);
@@ -375,4 +509,9 @@ O.#..O.#.#
     fn solution_b() {
         assert_eq!(solve_b(include_str!("input.txt")), 99641);
     }
+
+    #[test]
+    fn solution_check() {
+        assert!(check(include_str!("input.txt")).is_empty());
+    }
 }
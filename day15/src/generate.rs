@@ -0,0 +1,56 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::hash;
+
+/// Generates an initialization sequence of `size` steps, at least one, with
+/// the answer to part a.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<usize>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // reuse labels so lenses get replaced and removed
+    let labels = (0..=size / 3)
+        .map(|_| {
+            (0..rng.gen_range(2..=6))
+                .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    let steps = (0..size)
+        .map(|_| {
+            let label = labels.choose(&mut rng).unwrap();
+            if rng.gen_bool(0.7) {
+                format!("{label}={}", rng.gen_range(1..=9))
+            } else {
+                format!("{label}-")
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let answer_a = steps.iter().map(|s| hash(s)).sum();
+
+    (steps.join(","), Some(answer_a), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, _) = generate(1, 500);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert!(solve_b(&input) > 0);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, _) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert!(solve_b(&input) > 0);
+    }
+}
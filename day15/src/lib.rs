@@ -4,6 +4,10 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 use winnow::{
     ascii::{alpha1, dec_uint},
     combinator::{alt, preceded, separated},
@@ -19,7 +23,7 @@ pub fn solve_a(input: &str) -> u32 {
 fn hash(input: &str) -> u32 {
     input
         .chars()
-        .fold(0u32, |a, c| ((a + u32::try_from(c).unwrap()) * 17) % 256)
+        .fold(0u32, |a, c| ((a + u32::from(c)) * 17) % 256)
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -0,0 +1,104 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Steps for each direction a beam can head: east, south, west and north.
+const STEPS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const EAST: usize = 0;
+const SOUTH: usize = 1;
+const WEST: usize = 2;
+const NORTH: usize = 3;
+
+/// Generates a `size` by `size` contraption, at least 1 by 1, with the
+/// answers to both parts.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<usize>, Option<usize>) {
+    let size = size.max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let grid = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| match rng.gen_range(0..40) {
+                    0 => b'|',
+                    1 => b'-',
+                    2 => b'/',
+                    3 => b'\\',
+                    _ => b'.',
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let edges = (0..size).flat_map(|i| {
+        [
+            ((0, i), EAST),
+            ((size - 1, i), WEST),
+            ((i, 0), SOUTH),
+            ((i, size - 1), NORTH),
+        ]
+    });
+    let answer_b = edges.map(|start| energized(&grid, start)).max();
+
+    (
+        grid.iter()
+            .map(|l| String::from_utf8_lossy(l))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(energized(&grid, ((0, 0), EAST))),
+        answer_b,
+    )
+}
+
+/// The number of tiles a beam entering `start` heading in a direction from
+/// [`STEPS`] passes through.
+fn energized(grid: &[Vec<u8>], start: ((usize, usize), usize)) -> usize {
+    let size = grid.len();
+    let mut seen = vec![[false; 4]; size * size];
+    let mut beams = vec![start];
+
+    while let Some(((x, y), dir)) = beams.pop() {
+        if std::mem::replace(&mut seen[y * size + x][dir], true) {
+            continue;
+        }
+
+        let turns = match (grid[y][x], dir) {
+            (b'/', _) => vec![NORTH - dir],
+            (b'\\', _) => vec![dir ^ 1],
+            (b'|', EAST | WEST) => vec![NORTH, SOUTH],
+            (b'-', NORTH | SOUTH) => vec![EAST, WEST],
+            _ => vec![dir],
+        };
+        for turn in turns {
+            let (dx, dy) = STEPS[turn];
+            let next = (x.checked_add_signed(dx), y.checked_add_signed(dy));
+            if let (Some(x), Some(y)) = next {
+                if x < size && y < size {
+                    beams.push(((x, y), turn));
+                }
+            }
+        }
+    }
+
+    seen.iter().filter(|s| s.contains(&true)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_a, solve_b};
+
+    #[test]
+    fn generated_answers() {
+        let (input, a, b) = generate(1, 30);
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+
+    #[test]
+    fn smallest() {
+        let (input, a, b) = generate(1, 0);
+        assert_eq!(generate(1, 1).0, input);
+        assert_eq!(a, Some(1));
+        assert_eq!(Some(solve_a(&input)), a);
+        assert_eq!(Some(solve_b(&input)), b);
+    }
+}
@@ -0,0 +1,10 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Generates an input of `size`, with the answers to both parts where they
+/// are known by construction.
+#[must_use]
+pub fn generate(seed: u64, size: usize) -> (String, Option<u32>, Option<u32>) {
+    let _rng = StdRng::seed_from_u64(seed);
+
+    (".".repeat(size), None, None)
+}
@@ -4,6 +4,10 @@
 #![allow(clippy::missing_panics_doc)]
 #![warn(clippy::use_self)]
 
+mod generate;
+
+pub use generate::generate;
+
 #[must_use]
 pub fn solve_a(input: &str) -> u32 {
     input.len().try_into().unwrap()